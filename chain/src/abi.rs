@@ -1,10 +1,13 @@
 use alloc::string::String;
 use alloc::vec::Vec;
 
-use crate::amount_limit::AmountLimit;
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::amount_limit::AmountLimit;
+use crate::json::{JsonObject, NoStdDeserialize};
+
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ABI {
@@ -15,3 +18,14 @@ pub struct ABI {
     /// The limits on the amount
     pub amount_limit: Vec<AmountLimit>,
 }
+
+impl NoStdDeserialize for ABI {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(ABI {
+            name: object.field("name")?,
+            args: object.field("args")?,
+            amount_limit: object.field("amount_limit")?,
+        })
+    }
+}
@@ -11,6 +11,7 @@ use serde::{
     Deserialize, Serialize as SerSerialize,
 };
 
+use crate::json::{JsonObject, NoStdDeserialize};
 use crate::{Error, NumberBytes, Read, ReadError, SerializeData, Write, WriteError};
 
 #[derive(Clone, Default, Debug, PartialEq, Encode, Decode, SerializeData)]
@@ -102,6 +103,19 @@ impl<'de> serde::Deserialize<'de> for IostAction {
     }
 }
 
+impl NoStdDeserialize for IostAction {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(IostAction {
+            contract: object.field_or_default::<String>("contract")?.into_bytes(),
+            action_name: object
+                .field_or_default::<String>("action_name")?
+                .into_bytes(),
+            data: object.field_or_default::<String>("data")?.into_bytes(),
+        })
+    }
+}
+
 impl IostAction {
     pub fn new(contract: String, action_name: String, data: String) -> Self {
         IostAction {
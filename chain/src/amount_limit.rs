@@ -6,6 +6,7 @@ use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::json::{JsonObject, NoStdDeserialize};
 use crate::{NumberBytes, Read, SerializeData, Write};
 
 #[derive(Clone, Default, Debug, NumberBytes, Write, Read, SerializeData)]
@@ -19,6 +20,17 @@ pub struct AmountLimit {
     // #[serde(rename = "val")]
     pub value: String,
 }
+
+impl NoStdDeserialize for AmountLimit {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(AmountLimit {
+            token: object.field("token")?,
+            value: object.field("value")?,
+        })
+    }
+}
+
 //
 // #[cfg(feature = "std")]
 // impl<'de> serde::Deserialize<'de> for AmountLimit {
@@ -1,63 +1,67 @@
-// #[cfg(feature = "std")]
+use alloc::string::String;
+use alloc::vec::Vec;
 
-// use base64;
-// #[cfg(feature = "std")]
-// use serde::{Deserialize, Serialize};
-// use sha3::{Digest, Sha3_256};
-//
-// use alloc::collections::BTreeMap;
-// use alloc::format;
-// use alloc::string::String;
-// use alloc::vec::Vec;
-// use keys::algorithm;
-//
-// use crate::Error::IOSTBlockVerifyError;
-// use crate::Result;
-// use crate::SerializeData;
-// use crate::spv::{Head, Sign, Tx};
-// use crate::TxReceipt;
-//
-// #[derive(Debug)]
-// #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-// pub struct Block {
-//     /// block hash
-//     pub head: Head,
-//     /// block version number
-//     pub sign: Sign,
-//     /// public key of the block producer
-//     pub receipts: Vec<TxReceipt>,
-//     /// time of block production
-//     pub txs: Vec<Tx>,
-//     /// transaction number in the block
-//     #[cfg(feature = "std")]
-//     #[serde(rename = "txHashes")]
-//     pub tx_hashes: Vec<String>,
-//
-//     #[cfg(feature = "std")]
-//     #[serde(rename = "receiptHashes")]
-//     pub receipt_hashes: Vec<String>,
-//
-//     #[cfg(feature = "std")]
-//     #[serde(rename = "blockType")]
-//     pub block_type: String,
-// }
-//
-// impl Block {
-//     pub(crate) fn verify_self(&self) -> Result<()> {
-//         let ed25519 = algorithm::new(algorithm::ED25519);
-//         let sign = base64::decode(self.sign.sig.as_str()).unwrap();
-//         // #[cfg(feature = "std")]
-//         // let pub_key = self.head.witness.from_base58().unwrap();
-//         let pub_key = vec![];
-//         let hash = self.head.hash();
-//
-//         if !ed25519.verify(hash.as_slice(), pub_key.as_slice(), sign.as_slice()) {
-//             return Err(IOSTBlockVerifyError(format!("The signature of block {} is wrong", "EsoovjyX7ViMkbSs2y8gniJ7WQW33m8jVM8zvuZMKRRU")));
-//         }
-//
-//         if self.txs.len() != self.receipts.len() {
-//             return Err(IOSTBlockVerifyError(format!("Tx len {} unmatch receipt len {}", self.txs.len(), self.receipts.len())));
-//         }
-//         Ok(())
-//     }
-// }
\ No newline at end of file
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use crate::de::de_number_or_string_to_f64;
+use crate::info::Info;
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::transaction::Transaction;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Block {
+    /// block hash
+    pub hash: String,
+    /// block version number
+    pub version: String,
+    /// the hash of the parent block of this block
+    pub parent_hash: String,
+    /// the merkle tree hash of all transactions
+    pub tx_merkle_hash: String,
+    /// the merkle tree hash of all receipts
+    pub tx_receipt_merkle_hash: String,
+    /// block number
+    pub number: String,
+    /// public key of the block producer
+    pub witness: String,
+    /// time of block production
+    pub time: String,
+    /// total GAS consumption within the block
+    #[cfg_attr(
+        feature = "std",
+        serde(deserialize_with = "de_number_or_string_to_f64")
+    )]
+    pub gas_usage: f64,
+    /// transaction number in the block
+    pub tx_count: String,
+    /// (This key is reserved.)
+    #[cfg_attr(feature = "std", serde(default))]
+    pub info: Option<Info>,
+    /// all the transactions, only filled in when the block is fetched with `complete`
+    #[cfg_attr(feature = "std", serde(default))]
+    pub transactions: Vec<Transaction>,
+}
+
+impl NoStdDeserialize for Block {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(Block {
+            hash: object.field("hash")?,
+            version: object.field("version")?,
+            parent_hash: object.field("parent_hash")?,
+            tx_merkle_hash: object.field("tx_merkle_hash")?,
+            tx_receipt_merkle_hash: object.field("tx_receipt_merkle_hash")?,
+            number: object.field("number")?,
+            witness: object.field("witness")?,
+            time: object.field("time")?,
+            gas_usage: object.field("gas_usage")?,
+            tx_count: object.field("tx_count")?,
+            info: object.field_or_default("info")?,
+            transactions: object.field_or_default("transactions")?,
+        })
+    }
+}
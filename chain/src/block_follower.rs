@@ -0,0 +1,316 @@
+//! Following the chain block by block, reverting blocks that get replaced before they become
+//! irreversible.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::transport::HttpTransport;
+use crate::{Block, Error, IostClient, Result};
+
+#[derive(Debug, Clone)]
+pub enum BlockEvent {
+    /// a block was added on top of the followed chain
+    Applied(Block),
+    /// a pending block was replaced by a fork and is no longer on the chain
+    Reverted(Block),
+    /// an applied block became irreversible and will not be reverted
+    Irreversible(Block),
+}
+
+/// Position of a [`BlockFollower`]. Persist it after handling the events of a poll to resume
+/// from the same point after a restart.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FollowerCursor {
+    /// number of the next block to apply
+    pub next_block: i64,
+    /// hash of the last block reported irreversible, empty before the first one
+    pub irreversible_hash: String,
+    /// applied blocks that aren't irreversible yet, oldest first
+    pub pending: Vec<Block>,
+}
+
+impl FollowerCursor {
+    /// Start following at block `number`.
+    pub fn start_at(number: i64) -> Self {
+        FollowerCursor {
+            next_block: number,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FollowerConfig {
+    /// fetch blocks with their transactions
+    pub complete: bool,
+    /// upper bound for the blocks applied in one poll
+    pub max_blocks_per_poll: usize,
+}
+
+impl Default for FollowerConfig {
+    fn default() -> Self {
+        FollowerConfig {
+            complete: true,
+            max_blocks_per_poll: 100,
+        }
+    }
+}
+
+/// Follows the chain from a starting height towards `head_block`, using `getChainInfo` and
+/// `getBlockByNumber`.
+///
+/// Every block must link to the previous one by parent hash and number, as in
+/// `spv::check_witness`. When it doesn't, the previous block was replaced: pending blocks are
+/// reverted until the chain links up again. Blocks at or below `lib_block` are reported
+/// irreversible; a mismatch with an irreversible block is an error.
+pub struct BlockFollower<'a, T> {
+    client: &'a IostClient<T>,
+    config: FollowerConfig,
+    cursor: FollowerCursor,
+}
+
+impl<'a, T: HttpTransport> BlockFollower<'a, T> {
+    pub fn new(client: &'a IostClient<T>, cursor: FollowerCursor, config: FollowerConfig) -> Self {
+        BlockFollower {
+            client,
+            config,
+            cursor,
+        }
+    }
+
+    pub fn cursor(&self) -> &FollowerCursor {
+        &self.cursor
+    }
+
+    pub fn into_cursor(self) -> FollowerCursor {
+        self.cursor
+    }
+
+    /// Apply up to `max_blocks_per_poll` new blocks and report the ones that became
+    /// irreversible. A request that fails after some events were produced ends the poll
+    /// early with those events; the cursor always matches the events returned.
+    pub async fn poll(&mut self) -> Result<Vec<BlockEvent>> {
+        let mut events = Vec::new();
+        match self.step(&mut events).await {
+            Err(err) if events.is_empty() => Err(err),
+            _ => Ok(events),
+        }
+    }
+
+    async fn step(&mut self, events: &mut Vec<BlockEvent>) -> Result<()> {
+        let info = self.client.get_chain_info().await?;
+        let head = parse_number(&info.head_block)?;
+        let lib = parse_number(&info.lib_block)?;
+
+        if self.cursor.next_block > head {
+            self.check_tip(events).await?;
+        }
+        let mut applied = 0;
+        while self.cursor.next_block <= head && applied < self.config.max_blocks_per_poll {
+            let block = self
+                .client
+                .get_block_by_number(self.cursor.next_block, self.config.complete)
+                .await?
+                .block;
+            if self.links(&block)? {
+                self.cursor.next_block += 1;
+                self.cursor.pending.push(block.clone());
+                events.push(BlockEvent::Applied(block));
+                applied += 1;
+            } else {
+                self.revert_tip(events)?;
+            }
+        }
+
+        while let Some(first) = self.cursor.pending.first() {
+            if parse_number(&first.number)? > lib {
+                break;
+            }
+            let block = self.cursor.pending.remove(0);
+            self.cursor.irreversible_hash = block.hash.clone();
+            events.push(BlockEvent::Irreversible(block));
+        }
+        Ok(())
+    }
+
+    /// Whether `block` extends the followed chain.
+    fn links(&self, block: &Block) -> Result<bool> {
+        let number = parse_number(&block.number)?;
+        if number != self.cursor.next_block {
+            return Err(Error::IOSTBlockWitnessError(format!(
+                "invalid block number at block {}",
+                number
+            )));
+        }
+        let parent_hash = match self.cursor.pending.last() {
+            Some(tip) => &tip.hash,
+            None if self.cursor.irreversible_hash.is_empty() => return Ok(true),
+            None => &self.cursor.irreversible_hash,
+        };
+        Ok(&block.parent_hash == parent_hash)
+    }
+
+    /// When the follower is at the head, make sure its tip wasn't replaced at the same height.
+    async fn check_tip(&mut self, events: &mut Vec<BlockEvent>) -> Result<()> {
+        while let Some(tip) = self.cursor.pending.last() {
+            let number = parse_number(&tip.number)?;
+            let block = self.client.get_block_by_number(number, false).await?.block;
+            if block.hash == tip.hash {
+                break;
+            }
+            self.revert_tip(events)?;
+        }
+        Ok(())
+    }
+
+    fn revert_tip(&mut self, events: &mut Vec<BlockEvent>) -> Result<()> {
+        match self.cursor.pending.pop() {
+            Some(tip) => {
+                self.cursor.next_block -= 1;
+                events.push(BlockEvent::Reverted(tip));
+                Ok(())
+            }
+            None => Err(Error::IOSTBlockWitnessError(format!(
+                "invalid block hash at block {}, its parent is irreversible",
+                self.cursor.next_block
+            ))),
+        }
+    }
+}
+
+fn parse_number(number: &str) -> Result<i64> {
+    number
+        .parse()
+        .map_err(|_| Error::IOSTBlockVerifyError(format!("invalid block number {}", number)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{fixtures, MockTransport, Reply};
+    use crate::transport::block_on;
+    use alloc::string::ToString;
+
+    /// Serve a chain of block hashes by number, the parent of a block being the hash below
+    /// it, with `lib` the last irreversible block.
+    fn set_chain(transport: &MockTransport, hashes: &[&str], lib: i64) {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.to_string()).collect();
+        transport.set_head_block(hashes.len() as i64 - 1);
+        transport.set_lib_block(lib);
+        transport.set_handler("getBlockByNumber", move |request| {
+            let number: usize = request.segments()[1].parse().unwrap();
+            let mut body = fixtures::block(number as i64, false, "PENDING");
+            body["block"]["hash"] = hashes[number].as_str().into();
+            body["block"]["parent_hash"] = match number {
+                0 => "".into(),
+                _ => hashes[number - 1].as_str().into(),
+            };
+            Reply::ok(body)
+        });
+    }
+
+    fn chain(hashes: &[&str], lib: i64) -> IostClient<MockTransport> {
+        let transport = MockTransport::new();
+        set_chain(&transport, hashes, lib);
+        IostClient::with_transport("http://127.0.0.1:30001", transport)
+    }
+
+    fn describe(events: &[BlockEvent]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| match event {
+                BlockEvent::Applied(b) => format!("+{}", b.hash),
+                BlockEvent::Reverted(b) => format!("-{}", b.hash),
+                BlockEvent::Irreversible(b) => format!("!{}", b.hash),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_follow_and_finalize() {
+        let client = chain(&["g", "a1", "a2", "a3"], 2);
+        let mut follower = BlockFollower::new(
+            &client,
+            FollowerCursor::start_at(1),
+            FollowerConfig::default(),
+        );
+        let events = block_on(follower.poll()).unwrap();
+        assert_eq!(describe(&events), vec!["+a1", "+a2", "+a3", "!a1", "!a2"]);
+        assert_eq!(follower.cursor().next_block, 4);
+        assert_eq!(follower.cursor().irreversible_hash, "a2");
+        assert!(block_on(follower.poll()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_revert_replaced_blocks() {
+        let client = chain(&["g", "a1", "a2", "a3", "a4"], 1);
+        let mut follower = BlockFollower::new(
+            &client,
+            FollowerCursor::start_at(1),
+            FollowerConfig::default(),
+        );
+        block_on(follower.poll()).unwrap();
+
+        set_chain(client.transport(), &["g", "a1", "a2", "b3", "b4", "b5"], 2);
+        let events = block_on(follower.poll()).unwrap();
+        assert_eq!(
+            describe(&events),
+            vec!["-a4", "-a3", "+b3", "+b4", "+b5", "!a2"]
+        );
+    }
+
+    #[test]
+    fn should_revert_tip_replaced_at_same_height() {
+        let client = chain(&["g", "a1", "a2"], 1);
+        let mut follower = BlockFollower::new(
+            &client,
+            FollowerCursor::start_at(1),
+            FollowerConfig::default(),
+        );
+        block_on(follower.poll()).unwrap();
+
+        set_chain(client.transport(), &["g", "a1", "b2"], 1);
+        let events = block_on(follower.poll()).unwrap();
+        assert_eq!(describe(&events), vec!["-a2", "+b2"]);
+    }
+
+    #[test]
+    fn should_resume_from_cursor() {
+        let client = chain(&["g", "a1", "a2"], 0);
+        let mut follower = BlockFollower::new(
+            &client,
+            FollowerCursor::start_at(1),
+            FollowerConfig::default(),
+        );
+        block_on(follower.poll()).unwrap();
+        let saved = serde_json::to_string(&follower.into_cursor()).unwrap();
+
+        set_chain(client.transport(), &["g", "a1", "b2", "b3"], 1);
+        let cursor: FollowerCursor = serde_json::from_str(&saved).unwrap();
+        let mut follower = BlockFollower::new(&client, cursor, FollowerConfig::default());
+        let events = block_on(follower.poll()).unwrap();
+        assert_eq!(describe(&events), vec!["-a2", "+b2", "+b3", "!a1"]);
+    }
+
+    #[test]
+    fn should_fail_when_irreversible_block_is_replaced() {
+        let client = chain(&["g", "a1"], 1);
+        let mut follower = BlockFollower::new(
+            &client,
+            FollowerCursor::start_at(1),
+            FollowerConfig::default(),
+        );
+        block_on(follower.poll()).unwrap();
+
+        set_chain(client.transport(), &["g", "b1", "b2"], 1);
+        match block_on(follower.poll()) {
+            Err(Error::IOSTBlockWitnessError(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Blocking counterpart of the async [`IostClient`](crate::IostClient), for callers that
+//! don't run an async runtime.
+//!
+//! Like `reqwest::blocking`, it must not be used from within an async runtime.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::Range;
+use core::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::client;
+use crate::transport::{block_on, HttpResponse, HttpTransport};
+use crate::{
+    Account, BatchContractStorage, BatchContractStoragePost, BlockByHash, BlockByNumber,
+    CandidateBonus, ChainInfo, ClientConfig, Confirmation, Contract, ContractStorage,
+    ContractStorageFields, ContractStorageFieldsPost, ContractStoragePost, Error, Estimate,
+    GasRatio, GetTxByHash, NodeInfo, ProducerVoteInfo, RamInfo, Result, RetryPolicy, TokenBalance,
+    TokenInfo, TrackerConfig, Tx, TxReceipt, TxResponse, TxTracker, VoterBonus,
+};
+
+/// Transport backed by `reqwest::blocking::Client`. Requests complete before the returned
+/// future is first polled, so it can be driven with [`block_on`].
+#[derive(Clone, Default)]
+pub struct BlockingTransport {
+    client: reqwest::blocking::Client,
+}
+
+impl BlockingTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_client(client: reqwest::blocking::Client) -> Self {
+        BlockingTransport { client }
+    }
+
+    pub fn with_config(config: &ClientConfig) -> Result<Self> {
+        Ok(Self::with_client(config.build_blocking()?))
+    }
+}
+
+fn into_response(response: reqwest::blocking::Response) -> Result<HttpResponse> {
+    let status = response.status().as_u16();
+    let body = response.bytes().map_err(Error::Reqwest)?;
+    Ok(HttpResponse {
+        status,
+        body: body.to_vec(),
+    })
+}
+
+#[async_trait]
+impl HttpTransport for BlockingTransport {
+    async fn get(&self, url: &str) -> Result<HttpResponse> {
+        let response = self.client.get(url).send().map_err(Error::Reqwest)?;
+        into_response(response)
+    }
+
+    async fn post(&self, url: &str, body: Vec<u8>) -> Result<HttpResponse> {
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .map_err(Error::Reqwest)?;
+        into_response(response)
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration)
+    }
+}
+
+/// Blocking client for the HTTP API of an IOST node, e.g. `https://api.iost.io`.
+pub struct IostClient {
+    inner: client::IostClient<BlockingTransport>,
+}
+
+impl IostClient {
+    pub fn new(host: &str) -> Self {
+        Self::with_transport(host, BlockingTransport::new())
+    }
+
+    /// Client whose requests all share one connection pool, built from `config`.
+    pub fn with_config(host: &str, config: &ClientConfig) -> Result<Self> {
+        Ok(Self::with_transport(
+            host,
+            BlockingTransport::with_config(config)?,
+        ))
+    }
+
+    pub fn with_transport(host: &str, transport: BlockingTransport) -> Self {
+        IostClient {
+            inner: client::IostClient::with_transport(host, transport),
+        }
+    }
+
+    pub fn with_retry_policy(self, retry: RetryPolicy) -> Self {
+        IostClient {
+            inner: self.inner.with_retry_policy(retry),
+        }
+    }
+
+    pub fn host(&self) -> &str {
+        self.inner.host()
+    }
+
+    pub fn get_node_info(&self) -> Result<NodeInfo> {
+        block_on(self.inner.get_node_info())
+    }
+
+    pub fn get_chain_info(&self) -> Result<ChainInfo> {
+        block_on(self.inner.get_chain_info())
+    }
+
+    pub fn get_gas_ratio(&self) -> Result<GasRatio> {
+        block_on(self.inner.get_gas_ratio())
+    }
+
+    pub fn get_ram_info(&self) -> Result<RamInfo> {
+        block_on(self.inner.get_ram_info())
+    }
+
+    pub fn get_tx_by_hash(&self, hash: &str) -> Result<GetTxByHash> {
+        block_on(self.inner.get_tx_by_hash(hash))
+    }
+
+    pub fn get_tx_receipt_by_tx_hash(&self, hash: &str) -> Result<TxReceipt> {
+        block_on(self.inner.get_tx_receipt_by_tx_hash(hash))
+    }
+
+    pub fn get_block_by_hash(&self, hash: &str, complete: bool) -> Result<BlockByHash> {
+        block_on(self.inner.get_block_by_hash(hash, complete))
+    }
+
+    pub fn get_block_by_number(&self, number: i64, complete: bool) -> Result<BlockByNumber> {
+        block_on(self.inner.get_block_by_number(number, complete))
+    }
+
+    pub fn get_account(&self, name: &str, by_longest_chain: bool) -> Result<Account> {
+        block_on(self.inner.get_account(name, by_longest_chain))
+    }
+
+    pub fn get_token_balance(
+        &self,
+        account: &str,
+        token: &str,
+        by_longest_chain: bool,
+    ) -> Result<TokenBalance> {
+        block_on(
+            self.inner
+                .get_token_balance(account, token, by_longest_chain),
+        )
+    }
+
+    pub fn get_token_info(&self, symbol: &str, by_longest_chain: bool) -> Result<TokenInfo> {
+        block_on(self.inner.get_token_info(symbol, by_longest_chain))
+    }
+
+    pub fn get_contract(&self, id: &str, by_longest_chain: bool) -> Result<Contract> {
+        block_on(self.inner.get_contract(id, by_longest_chain))
+    }
+
+    pub fn get_contract_storage(&self, par: &ContractStoragePost) -> Result<ContractStorage> {
+        block_on(self.inner.get_contract_storage(par))
+    }
+
+    pub fn get_contract_storage_fields(
+        &self,
+        par: &ContractStorageFieldsPost,
+    ) -> Result<ContractStorageFields> {
+        block_on(self.inner.get_contract_storage_fields(par))
+    }
+
+    pub fn get_batch_contract_storage(
+        &self,
+        par: &BatchContractStoragePost,
+    ) -> Result<BatchContractStorage> {
+        block_on(self.inner.get_batch_contract_storage(par))
+    }
+
+    pub fn get_producer_vote_info(
+        &self,
+        account: &str,
+        by_longest_chain: bool,
+    ) -> Result<ProducerVoteInfo> {
+        block_on(self.inner.get_producer_vote_info(account, by_longest_chain))
+    }
+
+    pub fn get_candidate_bonus(
+        &self,
+        name: &str,
+        by_longest_chain: bool,
+    ) -> Result<CandidateBonus> {
+        block_on(self.inner.get_candidate_bonus(name, by_longest_chain))
+    }
+
+    pub fn get_voter_bonus(&self, name: &str, by_longest_chain: bool) -> Result<VoterBonus> {
+        block_on(self.inner.get_voter_bonus(name, by_longest_chain))
+    }
+
+    /// Blocks in `range` by number. Requests of the blocking transport complete one after the
+    /// other, whatever the concurrency.
+    pub fn get_blocks(
+        &self,
+        range: Range<i64>,
+        complete: bool,
+        concurrency: usize,
+    ) -> Vec<Result<BlockByNumber>> {
+        block_on(self.inner.get_blocks(range, complete, concurrency))
+    }
+
+    pub fn get_txs<S: AsRef<str>>(
+        &self,
+        hashes: &[S],
+        concurrency: usize,
+    ) -> Vec<Result<GetTxByHash>> {
+        block_on(self.inner.get_txs(hashes, concurrency))
+    }
+
+    pub fn get_accounts<S: AsRef<str>>(
+        &self,
+        names: &[S],
+        by_longest_chain: bool,
+        concurrency: usize,
+    ) -> Vec<Result<Account>> {
+        block_on(
+            self.inner
+                .get_accounts(names, by_longest_chain, concurrency),
+        )
+    }
+
+    pub fn get_token_balances<A: AsRef<str>, B: AsRef<str>>(
+        &self,
+        pairs: &[(A, B)],
+        by_longest_chain: bool,
+        concurrency: usize,
+    ) -> Vec<Result<TokenBalance>> {
+        block_on(
+            self.inner
+                .get_token_balances(pairs, by_longest_chain, concurrency),
+        )
+    }
+
+    /// Publish a signed transaction.
+    pub fn send_tx(&self, tx: &Tx) -> Result<TxResponse> {
+        block_on(self.inner.send_tx(tx))
+    }
+
+    /// Execute a transaction on the node without committing it, and return its receipt.
+    pub fn exec_tx(&self, tx: &Tx) -> Result<TxReceipt> {
+        block_on(self.inner.exec_tx(tx))
+    }
+
+    /// Dry-run `tx` and suggest a gas limit of the gas used times `margin`.
+    pub fn estimate_tx(&self, tx: &Tx, margin: f64) -> Result<Estimate> {
+        block_on(self.inner.estimate_tx(tx, margin))
+    }
+
+    /// Publish a signed transaction and wait until it reaches `config.level`.
+    pub fn send_and_confirm(&self, tx: &Tx, config: TrackerConfig) -> Result<Confirmation> {
+        block_on(self.inner.send_and_confirm(tx, config))
+    }
+
+    /// Publish a signed delayed transaction and wait until the deferred transaction created
+    /// from it executes and reaches `config.level`.
+    pub fn send_and_confirm_deferred(
+        &self,
+        tx: &Tx,
+        config: TrackerConfig,
+    ) -> Result<Confirmation> {
+        block_on(self.inner.send_and_confirm_deferred(tx, config))
+    }
+
+    /// Wait for the deferred transaction created from the already published delayed
+    /// transaction `tx`, see [`TxTracker::wait_deferred`].
+    pub fn wait_deferred(&self, tx: &Tx, config: TrackerConfig) -> Result<Confirmation> {
+        block_on(TxTracker::new(&self.inner, config).wait_deferred(tx))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_fail_without_node() {
+        // nothing listens on port 1, the request must fail instead of hanging
+        let client = IostClient::new("http://127.0.0.1:1").with_retry_policy(RetryPolicy::none());
+        match client.get_chain_info() {
+            Err(Error::Reqwest(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_undelayed_tx_before_sending() {
+        let client = IostClient::new("http://127.0.0.1:1").with_retry_policy(RetryPolicy::none());
+        let tx = Tx::builder()
+            .action(crate::IostAction::transfer("alice", "bob", "10", "").unwrap())
+            .time(1_000)
+            .build()
+            .unwrap();
+        let config = TrackerConfig::default();
+        assert!(matches!(
+            client.send_and_confirm_deferred(&tx, config.clone()),
+            Err(Error::InvalidTx(_))
+        ));
+        assert!(matches!(
+            client.wait_deferred(&tx, config),
+            Err(Error::InvalidTx(_))
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+//! Fetching many blocks, transactions, accounts or balances at once, with a bounded number of
+//! requests in flight.
+//!
+//! Results come back in the order of the input, one `Result` per item: a failed item doesn't
+//! abort the batch. Each request is retried on its own according to the client's
+//! [`RetryPolicy`](crate::RetryPolicy).
+
+use alloc::vec::Vec;
+use core::future::Future;
+use core::ops::Range;
+
+use futures_util::stream::{self, StreamExt};
+
+use crate::transport::HttpTransport;
+use crate::{Account, BlockByNumber, GetTxByHash, IostClient, Result, TokenBalance};
+
+/// concurrency of the bulk requests, unless a caller has a better idea
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Run `fetch` on every item with at most `concurrency` futures in flight, keeping the order.
+async fn fetch_all<I, F, Fut, R>(items: I, concurrency: usize, fetch: F) -> Vec<Result<R>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<R>>,
+{
+    stream::iter(items)
+        .map(fetch)
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+impl<T: HttpTransport> IostClient<T> {
+    /// Blocks in `range` by number, e.g. `1000..2000` when backfilling.
+    pub async fn get_blocks(
+        &self,
+        range: Range<i64>,
+        complete: bool,
+        concurrency: usize,
+    ) -> Vec<Result<BlockByNumber>> {
+        fetch_all(range, concurrency, |number| {
+            self.get_block_by_number(number, complete)
+        })
+        .await
+    }
+
+    pub async fn get_txs<S: AsRef<str>>(
+        &self,
+        hashes: &[S],
+        concurrency: usize,
+    ) -> Vec<Result<GetTxByHash>> {
+        fetch_all(hashes, concurrency, |hash| {
+            self.get_tx_by_hash(hash.as_ref())
+        })
+        .await
+    }
+
+    pub async fn get_accounts<S: AsRef<str>>(
+        &self,
+        names: &[S],
+        by_longest_chain: bool,
+        concurrency: usize,
+    ) -> Vec<Result<Account>> {
+        fetch_all(names, concurrency, |name| {
+            self.get_account(name.as_ref(), by_longest_chain)
+        })
+        .await
+    }
+
+    /// Balances of `(account, token)` pairs.
+    pub async fn get_token_balances<A: AsRef<str>, B: AsRef<str>>(
+        &self,
+        pairs: &[(A, B)],
+        by_longest_chain: bool,
+        concurrency: usize,
+    ) -> Vec<Result<TokenBalance>> {
+        fetch_all(pairs, concurrency, |(account, token)| {
+            self.get_token_balance(account.as_ref(), token.as_ref(), by_longest_chain)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::transport::block_on;
+    use crate::{Error, NodeErrorKind, RetryPolicy};
+    use alloc::string::ToString;
+
+    /// Serves blocks below 100, and tracks how many requests are in flight.
+    fn node() -> MockTransport {
+        let transport = MockTransport::new();
+        transport.set_head_block(99);
+        transport.overlap_requests();
+        transport
+    }
+
+    #[test]
+    fn should_keep_order_and_report_failures() {
+        let client = IostClient::with_transport("http://127.0.0.1:30001", node())
+            .with_retry_policy(RetryPolicy::none());
+        let blocks = block_on(client.get_blocks(95..105, false, 4));
+        assert_eq!(blocks.len(), 10);
+        for (number, block) in (95..105).zip(blocks) {
+            match block {
+                Ok(block) => assert_eq!(block.block.number, number.to_string()),
+                Err(Error::NodeError(NodeErrorKind::NotFound, _)) => assert!(number >= 100),
+                other => panic!("unexpected result for {}: {:?}", number, other),
+            }
+        }
+        assert_eq!(client.transport().max_in_flight(), 4);
+    }
+
+    #[test]
+    fn should_run_one_at_a_time() {
+        let client = IostClient::with_transport("http://127.0.0.1:30001", node());
+        let blocks = block_on(client.get_blocks(0..3, false, 0));
+        assert!(blocks.iter().all(|block| block.is_ok()));
+        assert_eq!(client.transport().max_in_flight(), 1);
+        assert!(block_on(client.get_blocks(5..5, false, 4)).is_empty());
+    }
+}
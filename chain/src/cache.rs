@@ -0,0 +1,434 @@
+//! Cache for chain data that can't change anymore: irreversible blocks, and the transactions
+//! and receipts in them.
+//!
+//! Entries live in a bounded in-memory LRU, and optionally in a directory of JSON files that
+//! outlives the process. Only responses with status `IRREVERSIBLE` are admitted, anything else
+//! is fetched from the node on every call.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::hash::Hash;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use lru::LruCache;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::transport::HttpTransport;
+use crate::{
+    Block, BlockByHash, BlockByNumber, Error, GetTxByHash, IostClient, Result, Status, TxReceipt,
+};
+
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// entries kept in memory for each of blocks, block numbers, transactions and receipts
+    pub capacity: usize,
+    /// directory of the on-disk store, none to only cache in memory
+    pub dir: Option<PathBuf>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            capacity: 1024,
+            dir: None,
+        }
+    }
+}
+
+/// Calls answered from the cache, and calls that went to the node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// A block as cached, `complete` if it was fetched with its transactions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedBlock {
+    complete: bool,
+    block: Block,
+}
+
+struct Memory {
+    blocks: LruCache<String, CachedBlock>,
+    numbers: LruCache<i64, String>,
+    txs: LruCache<String, GetTxByHash>,
+    receipts: LruCache<String, TxReceipt>,
+    stats: CacheStats,
+}
+
+const BLOCKS: &str = "blocks";
+const NUMBERS: &str = "numbers";
+const TXS: &str = "txs";
+const RECEIPTS: &str = "receipts";
+
+/// One JSON file per entry, in a subdirectory per kind of entry. The store is best effort:
+/// unreadable files are misses and failed writes are ignored.
+struct DiskStore {
+    dir: PathBuf,
+}
+
+impl DiskStore {
+    fn open(dir: &Path) -> Result<Self> {
+        for kind in &[BLOCKS, NUMBERS, TXS, RECEIPTS] {
+            fs::create_dir_all(dir.join(kind)).map_err(|err| Error::CacheError(err.to_string()))?;
+        }
+        Ok(DiskStore {
+            dir: dir.to_path_buf(),
+        })
+    }
+
+    /// Path of an entry, none for keys that aren't safe as file names. Hashes are base58.
+    fn path(&self, kind: &str, key: &str) -> Option<PathBuf> {
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        Some(self.dir.join(kind).join(format!("{}.json", key)))
+    }
+
+    fn get<V: DeserializeOwned>(&self, kind: &str, key: &str) -> Option<V> {
+        let data = fs::read(self.path(kind, key)?).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    fn put<V: Serialize>(&self, kind: &str, key: &str, value: &V) {
+        let path = match self.path(kind, key) {
+            Some(path) => path,
+            None => return,
+        };
+        if let Ok(data) = serde_json::to_vec(value) {
+            // write then rename, so readers never see a partial file
+            let tmp = path.with_extension("tmp");
+            if fs::write(&tmp, data).is_ok() {
+                let _ = fs::rename(&tmp, &path);
+            }
+        }
+    }
+}
+
+/// Client that answers `getBlockByHash`, `getBlockByNumber`, `getTxByHash` and
+/// `getTxReceiptByTxHash` from the cache once the data is irreversible.
+///
+/// A complete block also admits its transactions and their receipts. Receipts have no status
+/// of their own, so a receipt fetched with `get_tx_receipt_by_tx_hash` is never admitted.
+pub struct CachedClient<T> {
+    client: IostClient<T>,
+    memory: Mutex<Memory>,
+    disk: Option<DiskStore>,
+}
+
+impl<T: HttpTransport> CachedClient<T> {
+    /// Wrap `client`, creating the directory of the on-disk store if one is configured.
+    pub fn new(client: IostClient<T>, config: CacheConfig) -> Result<Self> {
+        let disk = match &config.dir {
+            Some(dir) => Some(DiskStore::open(dir)?),
+            None => None,
+        };
+        let capacity = config.capacity.max(1);
+        Ok(CachedClient {
+            client,
+            memory: Mutex::new(Memory {
+                blocks: LruCache::new(capacity),
+                numbers: LruCache::new(capacity),
+                txs: LruCache::new(capacity),
+                receipts: LruCache::new(capacity),
+                stats: CacheStats::default(),
+            }),
+            disk,
+        })
+    }
+
+    /// The wrapped client, for requests that aren't cached.
+    pub fn client(&self) -> &IostClient<T> {
+        &self.client
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.memory.lock().unwrap().stats
+    }
+
+    pub async fn get_block_by_hash(&self, hash: &str, complete: bool) -> Result<BlockByHash> {
+        if let Some(block) = self.cached_block(hash, complete) {
+            return Ok(BlockByHash {
+                status: Status::IRREVERSIBLE,
+                block,
+            });
+        }
+        self.count(false);
+        let response = self.client.get_block_by_hash(hash, complete).await?;
+        if response.status == Status::IRREVERSIBLE {
+            self.admit_block(&response.block, complete);
+        }
+        Ok(response)
+    }
+
+    pub async fn get_block_by_number(&self, number: i64, complete: bool) -> Result<BlockByNumber> {
+        let hash = self.lookup(|m| &mut m.numbers, NUMBERS, &number, &number.to_string());
+        if let Some(block) = hash.and_then(|hash| self.cached_block(&hash, complete)) {
+            return Ok(BlockByNumber {
+                status: Status::IRREVERSIBLE,
+                block,
+            });
+        }
+        self.count(false);
+        let response = self.client.get_block_by_number(number, complete).await?;
+        if response.status == Status::IRREVERSIBLE {
+            self.admit_block(&response.block, complete);
+        }
+        Ok(response)
+    }
+
+    pub async fn get_tx_by_hash(&self, hash: &str) -> Result<GetTxByHash> {
+        let cached = self.lookup(|m| &mut m.txs, TXS, &hash.to_string(), hash);
+        self.count(cached.is_some());
+        if let Some(tx) = cached {
+            return Ok(tx);
+        }
+        let response = self.client.get_tx_by_hash(hash).await?;
+        if response.status == Status::IRREVERSIBLE {
+            self.admit_tx(&response);
+        }
+        Ok(response)
+    }
+
+    pub async fn get_tx_receipt_by_tx_hash(&self, hash: &str) -> Result<TxReceipt> {
+        let cached = self.lookup(|m| &mut m.receipts, RECEIPTS, &hash.to_string(), hash);
+        self.count(cached.is_some());
+        match cached {
+            Some(receipt) => Ok(receipt),
+            None => self.client.get_tx_receipt_by_tx_hash(hash).await,
+        }
+    }
+
+    /// The cached block `hash`, if it has the transactions when they are asked for. Counts a
+    /// hit when found; a miss is counted by the caller, since a block number can miss before
+    /// the block is looked up.
+    fn cached_block(&self, hash: &str, complete: bool) -> Option<Block> {
+        let cached = self
+            .lookup(|m| &mut m.blocks, BLOCKS, &hash.to_string(), hash)
+            .filter(|cached| cached.complete || !complete)?;
+        self.count(true);
+        let mut block = cached.block;
+        if !complete {
+            block.transactions = Vec::new();
+        }
+        Some(block)
+    }
+
+    fn count(&self, hit: bool) {
+        let stats = &mut self.memory.lock().unwrap().stats;
+        if hit {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+    }
+
+    /// Look `key` up in memory, then on disk, promoting entries found on disk to memory.
+    fn lookup<K, V>(
+        &self,
+        map: impl Fn(&mut Memory) -> &mut LruCache<K, V>,
+        kind: &str,
+        key: &K,
+        file: &str,
+    ) -> Option<V>
+    where
+        K: Hash + Eq + Clone,
+        V: Clone + DeserializeOwned,
+    {
+        let mut memory = self.memory.lock().unwrap();
+        if let Some(value) = map(&mut memory).get(key) {
+            return Some(value.clone());
+        }
+        let value: V = self.disk.as_ref()?.get(kind, file)?;
+        map(&mut memory).put(key.clone(), value.clone());
+        Some(value)
+    }
+
+    fn store<K, V>(
+        &self,
+        map: impl Fn(&mut Memory) -> &mut LruCache<K, V>,
+        kind: &str,
+        key: K,
+        file: &str,
+        value: V,
+    ) where
+        K: Hash + Eq,
+        V: Serialize,
+    {
+        if let Some(disk) = &self.disk {
+            disk.put(kind, file, &value);
+        }
+        map(&mut self.memory.lock().unwrap()).put(key, value);
+    }
+
+    fn admit_block(&self, block: &Block, complete: bool) {
+        if let Ok(number) = block.number.parse::<i64>() {
+            self.store(
+                |m| &mut m.numbers,
+                NUMBERS,
+                number,
+                &block.number,
+                block.hash.clone(),
+            );
+        }
+        // don't let a block without transactions replace the complete one
+        let replaces = complete || self.cached_block_is_incomplete(&block.hash);
+        if replaces {
+            let cached = CachedBlock {
+                complete,
+                block: block.clone(),
+            };
+            self.store(
+                |m| &mut m.blocks,
+                BLOCKS,
+                block.hash.clone(),
+                &block.hash,
+                cached,
+            );
+        }
+        if complete {
+            for tx in &block.transactions {
+                self.admit_tx(&GetTxByHash {
+                    status: Status::IRREVERSIBLE,
+                    transaction: tx.clone(),
+                    block_number: block.number.clone(),
+                });
+            }
+        }
+    }
+
+    fn cached_block_is_incomplete(&self, hash: &str) -> bool {
+        !matches!(
+            self.lookup(|m| &mut m.blocks, BLOCKS, &hash.to_string(), hash),
+            Some(cached) if cached.complete
+        )
+    }
+
+    fn admit_tx(&self, tx: &GetTxByHash) {
+        let hash = &tx.transaction.hash;
+        if let Some(receipt) = &tx.transaction.tx_receipt {
+            self.store(
+                |m| &mut m.receipts,
+                RECEIPTS,
+                hash.clone(),
+                hash,
+                receipt.clone(),
+            );
+        }
+        self.store(|m| &mut m.txs, TXS, hash.clone(), hash, tx.clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{fixtures, MockTransport, Reply};
+    use crate::transport::block_on;
+
+    /// Node with blocks 1 (irreversible, tx `MockTx1`) and 2 (pending, tx `MockTx2`, packed).
+    /// Other transactions are irreversible.
+    fn node() -> MockTransport {
+        let transport = MockTransport::new();
+        transport.set_head_block(2);
+        transport.set_lib_block(1);
+        transport.set_response(
+            "getTxByHash/MockTx2",
+            Reply::ok(fixtures::tx_by_hash("MockTx2", 2, "PACKED")),
+        );
+        transport
+    }
+
+    fn cached(config: CacheConfig) -> CachedClient<MockTransport> {
+        let client = IostClient::with_transport("http://127.0.0.1:30001", node());
+        CachedClient::new(client, config).unwrap()
+    }
+
+    fn requests(client: &CachedClient<MockTransport>) -> usize {
+        client.client().transport().requests().len()
+    }
+
+    #[test]
+    fn should_only_cache_irreversible_data() {
+        let client = cached(CacheConfig::default());
+        for _ in 0..2 {
+            block_on(client.get_block_by_number(1, true)).unwrap();
+            block_on(client.get_block_by_number(2, true)).unwrap();
+            block_on(client.get_tx_by_hash("MockTx2")).unwrap();
+        }
+        assert_eq!(requests(&client), 5);
+        assert_eq!(client.stats(), CacheStats { hits: 1, misses: 5 });
+
+        // the complete block 1 admitted its transaction, and answers lookups by hash
+        let tx = block_on(client.get_tx_by_hash("MockTx1")).unwrap();
+        assert_eq!(tx.status, Status::IRREVERSIBLE);
+        assert_eq!(tx.block_number, "1");
+        let receipt = block_on(client.get_tx_receipt_by_tx_hash("MockTx1")).unwrap();
+        assert_eq!(receipt.tx_hash, "MockTx1");
+        let block = block_on(client.get_block_by_hash("MockBlock1", false)).unwrap();
+        assert!(block.block.transactions.is_empty());
+        assert_eq!(requests(&client), 5);
+
+        // receipts fetched on their own have no status and aren't admitted
+        block_on(client.get_tx_receipt_by_tx_hash("MockTx2")).unwrap();
+        block_on(client.get_tx_receipt_by_tx_hash("MockTx2")).unwrap();
+        assert_eq!(requests(&client), 7);
+    }
+
+    #[test]
+    fn should_fetch_transactions_missing_from_cached_block() {
+        let client = cached(CacheConfig::default());
+        block_on(client.get_block_by_hash("MockBlock1", false)).unwrap();
+        let block = block_on(client.get_block_by_hash("MockBlock1", true)).unwrap();
+        assert_eq!(block.block.transactions.len(), 1);
+        assert_eq!(requests(&client), 2);
+
+        block_on(client.get_block_by_hash("MockBlock1", false)).unwrap();
+        let block = block_on(client.get_block_by_number(1, true)).unwrap();
+        assert_eq!(block.block.transactions.len(), 1);
+        assert_eq!(requests(&client), 2);
+    }
+
+    #[test]
+    fn should_evict_least_recently_used() {
+        let client = cached(CacheConfig {
+            capacity: 1,
+            dir: None,
+        });
+        block_on(client.get_tx_by_hash("MockTx1")).unwrap();
+        block_on(client.get_block_by_number(1, false)).unwrap();
+        block_on(client.get_tx_by_hash("MockTx1")).unwrap();
+        assert_eq!(requests(&client), 2);
+
+        block_on(client.get_block_by_hash("MockBlock1", true)).unwrap();
+        // the block admitted MockTx1 again, so a different tx would evict it
+        assert_eq!(requests(&client), 3);
+        block_on(client.get_tx_by_hash("MockTx3")).unwrap();
+        block_on(client.get_tx_by_hash("MockTx1")).unwrap();
+        assert_eq!(requests(&client), 5);
+    }
+
+    #[test]
+    fn should_persist_to_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = CacheConfig {
+            capacity: 16,
+            dir: Some(dir.path().to_path_buf()),
+        };
+        let client = cached(config.clone());
+        block_on(client.get_block_by_number(1, true)).unwrap();
+        assert_eq!(requests(&client), 1);
+
+        let client = cached(config);
+        let block = block_on(client.get_block_by_number(1, true)).unwrap();
+        assert_eq!(block.status, Status::IRREVERSIBLE);
+        assert_eq!(block.block.hash, fixtures::block_hash(1));
+        let tx = block_on(client.get_tx_by_hash("MockTx1")).unwrap();
+        assert_eq!(tx.transaction.tx_receipt.unwrap().gas_usage, 2577.0);
+        block_on(client.get_tx_receipt_by_tx_hash("MockTx1")).unwrap();
+        assert_eq!(requests(&client), 0);
+    }
+}
@@ -1,225 +1,606 @@
-//
-// use alloc::format;
-// use alloc::string::String;
-// #[cfg(feature = "std")]
-// use async_trait::async_trait;
-//
-// #[cfg(feature = "std")]
-// use serde::{Deserialize, Serialize};
-// use crate::{Error, NodeInfo, ErrorMessage, ChainInfo, GasRatio, RamInfo, ContractStoragePost, ContractStorage, ContractStorageFieldsPost, ContractStorageFields, BatchContractStoragePost, BatchContractStorage};
-// use crate::tx_response::TxResponse;
-// use crate::tx::Tx;
-//
-// struct IOST {
-//     host: String,
-//     client: reqwest::Client,
-// }
-//
-// #[async_trait]
-// trait Client {
-//     fn new(host: &str) -> Self;
-//
-//     async fn get<T>(&self, path: &str) -> Result<T, Error>
-//         where
-//             T: 'static + for<'de> Deserialize<'de>;
-//
-//     async fn post<T, R>(&self, path: &str, param: R) -> Result<T, Error>
-//         where
-//             T: 'static + for<'de> Deserialize<'de>,
-//             R: Serialize + Send + Sync;
-// }
-//
-// #[async_trait]
-// impl Client for IOST {
-//     fn new(host: &str) -> Self {
-//         Self {
-//             host: host.to_owned(),
-//             client: reqwest::Client::new(),
-//         }
-//     }
-//
-//     async fn get<T>(&self, path: &str) -> Result<T, Error>
-//         where
-//             T: 'static + for<'de> Deserialize<'de>,
-//     {
-//         let url = format!("{}/{}", self.host, path);
-//         let response = self.client.get(&url).send().await.map_err(Error::Reqwest)?;
-//         if response.status() == 200 {
-//             let result = response.json::<T>().await.map_err(Error::Reqwest)?;
-//             Ok(result)
-//         } else {
-//             let rsp = response
-//                 .json::<ErrorMessage>()
-//                 .await
-//                 .map_err(Error::Reqwest)?;
-//             Err(Error::ErrorMessage(rsp))
-//         }
-//     }
-//
-//     async fn post<T, R>(&self, path: &str, param: R) -> Result<T, Error>
-//         where
-//             T: 'static + for<'de> Deserialize<'de>,
-//             R: Serialize + Send + Sync,
-//     {
-//         let url = format!("{}/{}", self.host, path);
-//         let req = reqwest::Client::new()
-//             .post(&url)
-//             .json(&param)
-//             .send()
-//             .await
-//             .map_err(Error::Reqwest)?;
-//         let code_status = req.status();
-//         if code_status == 200 {
-//             let response = req.json().await.map_err(Error::Reqwest)?;
-//             Ok(response)
-//         } else {
-//             let response = req.json().await.map_err(Error::Reqwest)?;
-//             Err(Error::ErrorMessage(response))
-//         }
-//     }
-// }
-//
-// impl IOST {
-//     pub async fn get_node_info(&self) -> Result<NodeInfo, Error> {
-//         self.get("getNodeInfo").await
-//     }
-//
-//     pub async fn get_chain_info(&self) -> Result<ChainInfo, Error> {
-//         self.get("getChainInfo").await
-//     }
-//
-//     pub async fn get_gas_ratio(&self) -> Result<GasRatio, Error> {
-//         self.get("getGasRatio").await
-//     }
-//
-//     pub async fn get_ram_info(&self) -> Result<RamInfo, Error> {
-//         self.get("getRAMInfo").await
-//     }
-//
-//     pub async fn get_contract_storage(
-//         &self,
-//         par: ContractStoragePost,
-//     ) -> Result<ContractStorage, Error> {
-//         self.post("getContractStorage", &par).await
-//     }
-//
-//     pub async fn get_contract_storage_fields(
-//         &self,
-//         par: ContractStorageFieldsPost,
-//     ) -> Result<ContractStorageFields, Error> {
-//         self.post("getContractStorageFields", &par).await
-//     }
-//
-//     pub async fn get_batch_contract_storage(
-//         &self,
-//         par: BatchContractStoragePost,
-//     ) -> Result<BatchContractStorage, Error> {
-//         self.post("getBatchContractStorage", &par).await
-//     }
-//
-//     pub async fn send_tx(&self, par: Tx) -> Result<TxResponse, Error> {
-//         self.post("sendTx", par).await
-//     }
-// }
-//
-// /// Execute test file command "cargo test iost_basic_test -- --nocapture"
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use crate::action::Action;
-//     use crate::amount_limit::AmountLimit;
-//     use crate::signature::Signature;
-//     use crate::{KeyField, BatchContractStoragePost};
-//
-//     #[tokio::test]
-//     async fn iost_basic_get_method_should_be_ok() {
-//         let host = "https://api.iost.io";
-//         let iost = IOST::new(host);
-//         let result = iost.get_node_info().await;
-//         assert!(result.is_ok());
-//         let chain_result = iost.get_chain_info().await;
-//         assert!(chain_result.is_ok());
-//         let gas_result = iost.get_gas_ratio().await;
-//         assert!(gas_result.is_ok());
-//         let ram_result = iost.get_ram_info().await;
-//         assert!(ram_result.is_ok());
-//     }
-//
-//     #[tokio::test]
-//     async fn iost_basic_post_method_should_be_ok() {
-//         let host = "https://api.iost.io";
-//         let iost = IOST::new(host);
-//         let new_post = ContractStoragePost {
-//             id: "token.iost".to_string(),
-//             key: "TIiost".to_string(),
-//             field: "decimal".to_string(),
-//             by_longest_chain: true,
-//         };
-//         let result = iost.get_contract_storage(new_post).await;
-//         assert!(result.is_ok());
-//         let new_post = ContractStorageFieldsPost {
-//             id: "token.iost".to_string(),
-//             key: "TIiost".to_string(),
-//             by_longest_chain: true,
-//         };
-//         let field_result = iost.get_contract_storage_fields(new_post).await;
-//         assert!(field_result.is_ok());
-//         let key = KeyField {
-//             key: "supply".to_string(),
-//             field: "TIiost".to_string(),
-//         };
-//
-//         let key1 = KeyField {
-//             key: "decimal".to_string(),
-//             field: "TIiost".to_string(),
-//         };
-//
-//         let posts = BatchContractStoragePost {
-//             id: "token.iost".to_string(),
-//             key_fields: vec![key, key1],
-//             by_longest_chain: true,
-//         };
-//         let storage_result = iost.get_batch_contract_storage(posts).await;
-//         assert!(storage_result.is_ok());
-//     }
-//
-//     #[tokio::test]
-//     async fn test_send_tx_should_be_ok() {
-//         let host = "https://api.iost.io";
-//         let iost = IOST::new(host);
-//         let action = Action{
-//             contract: "token.iost".to_string(),
-//             action_name: "transfer".to_string(),
-//             data: "[\"iost\", \"testaccount\", \"anothertest\", \"100\", \"this is an example transfer\"]".to_string()
-//         };
-//
-//         let amount_limit = AmountLimit {
-//             token: "*".to_string(),
-//             value: "unlimited".to_string(),
-//         };
-//
-//         let signature = Signature{
-//             algorithm: "ED25519".to_string(),
-//             signature: "lDS+SdM+aiVHbDyXapvrsgyKxFg9mJuHWPZb/INBRWY=".to_string(),
-//             public_key: "/K1HM0OEbfJ4+D3BmalpLmb03WS7BeCz4nVHBNbDrx3/A31aN2RJNxyEKhv+VSoWctfevDNRnL1kadRVxSt8CA==".to_string()
-//         };
-//
-//         let tx = Tx {
-//             time: 1544709662543340000,
-//             expiration: 1544709692318715000,
-//             gas_ratio: 1.0,
-//             gas_limit: 500000.0,
-//             delay: 0,
-//             chain_id: 1024,
-//             actions: vec![action],
-//             amount_limit: vec![amount_limit],
-//             publisher: "testaccount".to_string(),
-//             publisher_sigs: vec![signature],
-//             signers: vec![],
-//             signatures: vec![],
-//         };
-//
-//         let tx_result = iost.send_tx(tx).await;
-//         dbg!(tx_result);
-//     }
-// }
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::future::Future;
+
+use futures_core::Stream;
+#[cfg(feature = "std")]
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::field::Empty;
+use tracing::Instrument;
+
+use crate::estimate::Estimate;
+#[cfg(not(feature = "std"))]
+use crate::json::{self, NoStdDeserialize};
+use crate::node_error::{is_transient, NodeErrorKind};
+use crate::retry::{RetryPolicy, RetryTimer};
+use crate::subscribe::{self, Event, SubscribeRequest};
+use crate::telemetry::RequestTelemetry;
+#[cfg(feature = "std")]
+use crate::transport::timeout;
+#[cfg(feature = "client")]
+use crate::transport::ReqwestTransport;
+use crate::transport::{HttpResponse, HttpTransport};
+use crate::tx_tracker::{Confirmation, TrackerConfig, TxTracker};
+#[cfg(feature = "client")]
+use crate::ClientConfig;
+use crate::{
+    Account, BatchContractStorage, BatchContractStoragePost, BlockByHash, BlockByNumber,
+    CandidateBonus, ChainInfo, Contract, ContractStorage, ContractStorageFields,
+    ContractStorageFieldsPost, ContractStoragePost, Error, ErrorMessage, GasRatio, GetTxByHash,
+    NodeInfo, ProducerVoteInfo, RamInfo, Result, TokenBalance, TokenInfo, Tx, TxReceipt,
+    TxResponse, VoterBonus,
+};
+
+/// Decoding of response bodies: serde_json with `std`, lite-json without.
+pub(crate) trait ResponseBody: Sized {
+    fn from_body(body: &[u8]) -> Result<Self>;
+}
+
+#[cfg(feature = "std")]
+impl<T: DeserializeOwned> ResponseBody for T {
+    fn from_body(body: &[u8]) -> Result<Self> {
+        serde_json::from_slice(body).map_err(|_| Error::JsonParserError())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<T: NoStdDeserialize> ResponseBody for T {
+    fn from_body(body: &[u8]) -> Result<Self> {
+        json::from_slice(body)
+    }
+}
+
+/// Encoding of request bodies: serde_json with `std`, lite-json without.
+pub(crate) trait RequestBody {
+    fn to_body(&self) -> Result<Vec<u8>>;
+}
+
+#[cfg(feature = "std")]
+impl<T: Serialize + ?Sized> RequestBody for T {
+    fn to_body(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|_| Error::JsonParserError())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl RequestBody for Tx {
+    fn to_body(&self) -> Result<Vec<u8>> {
+        Ok(self.clone().no_std_serialize_vec())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl RequestBody for ContractStoragePost {
+    fn to_body(&self) -> Result<Vec<u8>> {
+        use lite_json::Serialize;
+        Ok(self.no_std_serialize().serialize())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl RequestBody for ContractStorageFieldsPost {
+    fn to_body(&self) -> Result<Vec<u8>> {
+        use lite_json::Serialize;
+        Ok(self.no_std_serialize().serialize())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl RequestBody for BatchContractStoragePost {
+    fn to_body(&self) -> Result<Vec<u8>> {
+        use lite_json::Serialize;
+        Ok(self.no_std_serialize().serialize())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl RequestBody for SubscribeRequest {
+    fn to_body(&self) -> Result<Vec<u8>> {
+        use lite_json::Serialize;
+        Ok(self.no_std_serialize().serialize())
+    }
+}
+
+/// Async client for the HTTP API of an IOST node, e.g. `https://api.iost.io`.
+///
+/// Requests go through an [`HttpTransport`]; `IostClient::new` uses reqwest. Requests that fail
+/// with a transient error are retried according to the [`RetryPolicy`].
+pub struct IostClient<T> {
+    host: String,
+    transport: T,
+    retry: RetryPolicy,
+}
+
+#[cfg(feature = "client")]
+impl IostClient<ReqwestTransport> {
+    pub fn new(host: &str) -> Self {
+        Self::with_transport(host, ReqwestTransport::new())
+    }
+
+    /// Client whose requests all share one connection pool, built from `config`.
+    pub fn with_config(host: &str, config: &ClientConfig) -> Result<Self> {
+        Ok(Self::with_transport(
+            host,
+            ReqwestTransport::with_config(config)?,
+        ))
+    }
+}
+
+impl<T: HttpTransport> IostClient<T> {
+    pub fn with_transport(host: &str, transport: T) -> Self {
+        Self {
+            host: host.trim_end_matches('/').to_string(),
+            transport,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    async fn get<R>(&self, path: &str) -> Result<R>
+    where
+        R: ResponseBody,
+    {
+        let url = format!("{}/{}", self.host, path);
+        let telemetry = RequestTelemetry::start(&self.host, path);
+        let result = self
+            .retrying(|_| async {
+                let response = self.transport.get(&url).await?;
+                telemetry.status(response.status);
+                decode_response(response)
+            })
+            .instrument(telemetry.span())
+            .await;
+        telemetry.finish(&result);
+        result
+    }
+
+    async fn post<R, B>(&self, path: &str, param: &B) -> Result<R>
+    where
+        R: ResponseBody,
+        B: RequestBody + ?Sized,
+    {
+        self.post_recovering(path, param, |_| None).await
+    }
+
+    /// `post`, answering with `recover(err)` when a retry fails with an error `recover` turns
+    /// into a response. A retry can fail because an earlier attempt got through after all.
+    async fn post_recovering<R, B, E>(&self, path: &str, param: &B, recover: E) -> Result<R>
+    where
+        R: ResponseBody,
+        B: RequestBody + ?Sized,
+        E: Fn(&Error) -> Option<R>,
+    {
+        let url = format!("{}/{}", self.host, path);
+        let body = param.to_body()?;
+        let telemetry = RequestTelemetry::start(&self.host, path);
+        let result = self
+            .retrying(|retry| {
+                let (url, body, telemetry, recover) = (&url, &body, &telemetry, &recover);
+                async move {
+                    let result = async {
+                        let response = self.transport.post(url, body.clone()).await?;
+                        telemetry.status(response.status);
+                        decode_response(response)
+                    }
+                    .await;
+                    match result {
+                        Err(err) if retry > 0 => recover(&err).ok_or(err),
+                        result => result,
+                    }
+                }
+            })
+            .instrument(telemetry.span())
+            .await;
+        telemetry.finish(&result);
+        result
+    }
+
+    /// Run `request` until it succeeds, fails for good, or the retry policy gives up.
+    /// `request` gets the number of the retry, 0 for the first attempt.
+    async fn retrying<F, Fut, R>(&self, request: F) -> Result<R>
+    where
+        F: Fn(u32) -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
+        let timer = RetryTimer::start();
+        let mut retry = 0;
+        loop {
+            let err = match self.attempt(&timer, request(retry)).await {
+                Ok(result) => return Ok(result),
+                Err(err) => err,
+            };
+            let backoff = self.retry.backoff(retry);
+            if retry >= self.retry.max_retries
+                || !is_transient(&err)
+                || !timer.allows(&self.retry, backoff)
+            {
+                return Err(err);
+            }
+            self.transport.sleep(backoff).await;
+            retry += 1;
+        }
+    }
+
+    /// Run one attempt of a request, abandoning it at the deadline.
+    #[cfg(feature = "std")]
+    async fn attempt<Fut, R>(&self, timer: &RetryTimer, attempt: Fut) -> Result<R>
+    where
+        Fut: Future<Output = Result<R>>,
+    {
+        match (timer.remaining(&self.retry), self.retry.deadline) {
+            (Some(remaining), Some(deadline)) => timeout(&self.transport, remaining, attempt)
+                .await
+                .unwrap_or(Err(Error::RequestTimeout(deadline))),
+            _ => attempt.await,
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    async fn attempt<Fut, R>(&self, _timer: &RetryTimer, attempt: Fut) -> Result<R>
+    where
+        Fut: Future<Output = Result<R>>,
+    {
+        attempt.await
+    }
+
+    pub async fn get_node_info(&self) -> Result<NodeInfo> {
+        self.get("getNodeInfo").await
+    }
+
+    pub async fn get_chain_info(&self) -> Result<ChainInfo> {
+        self.get("getChainInfo").await
+    }
+
+    pub async fn get_gas_ratio(&self) -> Result<GasRatio> {
+        self.get("getGasRatio").await
+    }
+
+    pub async fn get_ram_info(&self) -> Result<RamInfo> {
+        self.get("getRAMInfo").await
+    }
+
+    pub async fn get_tx_by_hash(&self, hash: &str) -> Result<GetTxByHash> {
+        self.get(&format!("getTxByHash/{}", hash)).await
+    }
+
+    pub async fn get_tx_receipt_by_tx_hash(&self, hash: &str) -> Result<TxReceipt> {
+        self.get(&format!("getTxReceiptByTxHash/{}", hash)).await
+    }
+
+    pub async fn get_block_by_hash(&self, hash: &str, complete: bool) -> Result<BlockByHash> {
+        self.get(&format!("getBlockByHash/{}/{}", hash, complete))
+            .await
+    }
+
+    pub async fn get_block_by_number(&self, number: i64, complete: bool) -> Result<BlockByNumber> {
+        self.get(&format!("getBlockByNumber/{}/{}", number, complete))
+            .await
+    }
+
+    pub async fn get_account(&self, name: &str, by_longest_chain: bool) -> Result<Account> {
+        self.get(&format!("getAccount/{}/{}", name, by_longest_chain))
+            .await
+    }
+
+    pub async fn get_token_balance(
+        &self,
+        account: &str,
+        token: &str,
+        by_longest_chain: bool,
+    ) -> Result<TokenBalance> {
+        self.get(&format!(
+            "getTokenBalance/{}/{}/{}",
+            account, token, by_longest_chain
+        ))
+        .await
+    }
+
+    pub async fn get_token_info(&self, symbol: &str, by_longest_chain: bool) -> Result<TokenInfo> {
+        self.get(&format!("getTokenInfo/{}/{}", symbol, by_longest_chain))
+            .await
+    }
+
+    pub async fn get_contract(&self, id: &str, by_longest_chain: bool) -> Result<Contract> {
+        self.get(&format!("getContract/{}/{}", id, by_longest_chain))
+            .await
+    }
+
+    pub async fn get_contract_storage(&self, par: &ContractStoragePost) -> Result<ContractStorage> {
+        self.post("getContractStorage", par).await
+    }
+
+    pub async fn get_contract_storage_fields(
+        &self,
+        par: &ContractStorageFieldsPost,
+    ) -> Result<ContractStorageFields> {
+        self.post("getContractStorageFields", par).await
+    }
+
+    pub async fn get_batch_contract_storage(
+        &self,
+        par: &BatchContractStoragePost,
+    ) -> Result<BatchContractStorage> {
+        self.post("getBatchContractStorage", par).await
+    }
+
+    pub async fn get_producer_vote_info(
+        &self,
+        account: &str,
+        by_longest_chain: bool,
+    ) -> Result<ProducerVoteInfo> {
+        self.get(&format!(
+            "getProducerVoteInfo/{}/{}",
+            account, by_longest_chain
+        ))
+        .await
+    }
+
+    pub async fn get_candidate_bonus(
+        &self,
+        name: &str,
+        by_longest_chain: bool,
+    ) -> Result<CandidateBonus> {
+        self.get(&format!("getCandidateBonus/{}/{}", name, by_longest_chain))
+            .await
+    }
+
+    pub async fn get_voter_bonus(&self, name: &str, by_longest_chain: bool) -> Result<VoterBonus> {
+        self.get(&format!("getVoterBonus/{}/{}", name, by_longest_chain))
+            .await
+    }
+
+    /// Publish a signed transaction. An attempt that fails in transit may still have reached
+    /// the node, so a retry rejected as a duplicate counts as published, under the hash of
+    /// `tx`.
+    pub async fn send_tx(&self, tx: &Tx) -> Result<TxResponse> {
+        let span = tracing::debug_span!("iost_send_tx", tx_hash = Empty);
+        let published = |err: &Error| match err.node_error_kind() {
+            Some(NodeErrorKind::DuplicateTx) => tx.hash().ok().map(|hash| TxResponse {
+                hash: hash.to_string(),
+                pre_tx_receipt: None,
+            }),
+            _ => None,
+        };
+        let response: TxResponse = self
+            .post_recovering("sendTx", tx, published)
+            .instrument(span.clone())
+            .await?;
+        span.record("tx_hash", response.hash.as_str());
+        Ok(response)
+    }
+
+    /// Execute a transaction on the node without committing it, and return its receipt.
+    pub async fn exec_tx(&self, tx: &Tx) -> Result<TxReceipt> {
+        let span = tracing::debug_span!("iost_exec_tx", tx_hash = Empty);
+        let receipt: TxReceipt = self.post("execTx", tx).instrument(span.clone()).await?;
+        span.record("tx_hash", receipt.tx_hash.as_str());
+        Ok(receipt)
+    }
+
+    /// Dry-run `tx` with `exec_tx` and suggest a gas limit of the gas used times `margin`,
+    /// see [`Estimate`]. Build `tx` with a generous gas limit, so the dry run doesn't run out.
+    pub async fn estimate_tx(&self, tx: &Tx, margin: f64) -> Result<Estimate> {
+        let receipt = self.exec_tx(tx).await?;
+        Estimate::from_receipt(receipt, margin)
+    }
+
+    /// Publish a signed transaction and wait until it reaches `config.level`, see
+    /// [`TxTracker`].
+    pub async fn send_and_confirm(&self, tx: &Tx, config: TrackerConfig) -> Result<Confirmation> {
+        let response = self.send_tx(tx).await?;
+        TxTracker::new(self, config)
+            .wait(&response.hash, Some(tx.expiration))
+            .await
+    }
+
+    /// Publish a signed delayed transaction and wait until the deferred transaction created
+    /// from it executes and reaches `config.level`, see [`TxTracker::wait_deferred`].
+    pub async fn send_and_confirm_deferred(
+        &self,
+        tx: &Tx,
+        config: TrackerConfig,
+    ) -> Result<Confirmation> {
+        // fail before publishing a transaction that can't be deferred
+        tx.deferred()?;
+        self.send_tx(tx).await?;
+        TxTracker::new(self, config).wait_deferred(tx).await
+    }
+
+    /// Stream the contract events or receipts matching `request` as the node emits them.
+    ///
+    /// The stream reconnects when the connection drops, backing off according to the
+    /// [`RetryPolicy`]; it ends with an error once `max_retries` reconnects in a row failed,
+    /// or on a permanent error. The policy's deadline doesn't apply. Events the node delivers
+    /// again after a reconnect are skipped, and so are events at or before `resume_after`,
+    /// which lets a consumer restart from the time of the last event it processed. The node
+    /// doesn't replay events emitted while disconnected; read the blocks in between to fill
+    /// such gaps.
+    pub fn subscribe<'a>(
+        &'a self,
+        request: &SubscribeRequest,
+        resume_after: Option<i64>,
+    ) -> impl Stream<Item = Result<Event>> + 'a {
+        subscribe::subscribe(self, request, resume_after)
+    }
+}
+
+fn decode_response<R: ResponseBody>(response: HttpResponse) -> Result<R> {
+    if response.status == 200 {
+        R::from_body(&response.body)
+    } else {
+        Err(error_response(response.status, &response.body))
+    }
+}
+
+/// The classified error for a response with a status other than 200.
+pub(crate) fn error_response(status: u16, body: &[u8]) -> Error {
+    let rsp = match ErrorMessage::from_body(body) {
+        Ok(rsp) => rsp,
+        Err(_) => ErrorMessage {
+            code: 0,
+            message: String::from_utf8_lossy(body).into_owned(),
+        },
+    };
+    let kind = NodeErrorKind::classify(status, &rsp);
+    Error::NodeError(kind, rsp)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{MockTransport, Reply};
+    use crate::transport::block_on;
+    use core::time::Duration;
+
+    #[test]
+    fn should_get_through_transport() {
+        let client = IostClient::with_transport("http://127.0.0.1:30001/", MockTransport::new());
+        let gas_ratio = block_on(client.get_gas_ratio()).unwrap();
+        assert_eq!(gas_ratio.lowest_gas_ratio, 1.0);
+        assert_eq!(gas_ratio.median_gas_ratio, 1.0);
+        let requests = client.transport().requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].url, "http://127.0.0.1:30001/getGasRatio");
+    }
+
+    #[test]
+    fn should_post_through_transport() {
+        let client = IostClient::with_transport("http://127.0.0.1:30001", MockTransport::new());
+        let tx = Tx::new(0, 0, 1024, vec![]);
+        let response = block_on(client.send_tx(&tx)).unwrap();
+        assert_eq!(response.hash, tx.hash().unwrap().to_string());
+        assert!(response.pre_tx_receipt.is_none());
+        let requests = client.transport().requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].url, "http://127.0.0.1:30001/sendTx");
+        let sent: Tx = serde_json::from_slice(requests[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!(sent.hash().unwrap(), tx.hash().unwrap());
+    }
+
+    #[test]
+    fn should_classify_error_response() {
+        let transport = MockTransport::new();
+        transport.set_response("getTxByHash", Reply::error(500, 5, "tx not found"));
+        let client = IostClient::with_transport("http://127.0.0.1:30001", transport);
+        match block_on(client.get_tx_by_hash("abc")) {
+            Err(Error::NodeError(kind, msg)) => {
+                assert_eq!(kind, NodeErrorKind::NotFound);
+                assert_eq!(msg.message, "tx not found");
+            }
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+        assert_eq!(client.transport().requests().len(), 1);
+    }
+
+    #[test]
+    fn should_retry_transient_errors() {
+        let transport = MockTransport::new();
+        transport.set_responses(
+            "sendTx",
+            vec![
+                Reply::text(503, "<html>Service Unavailable</html>"),
+                Reply::error(429, 8, "rate limit exceeded"),
+                Reply::Fixture,
+            ],
+        );
+        let client = IostClient::with_transport("http://127.0.0.1:30001", transport);
+        let tx = Tx::new(0, 0, 1024, vec![]);
+        let response = block_on(client.send_tx(&tx)).unwrap();
+        assert_eq!(response.hash, tx.hash().unwrap().to_string());
+        assert_eq!(
+            client.transport().sleeps(),
+            vec![Duration::from_millis(200), Duration::from_millis(400)]
+        );
+    }
+
+    #[test]
+    fn should_not_retry_permanent_errors() {
+        let transport = MockTransport::new();
+        transport.set_response("sendTx", Reply::error(500, 2, "tx err:DupError"));
+        let client = IostClient::with_transport("http://127.0.0.1:30001", transport);
+        let tx = Tx::new(0, 0, 1024, vec![]);
+        match block_on(client.send_tx(&tx)) {
+            Err(Error::NodeError(NodeErrorKind::DuplicateTx, _)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(client.transport().requests().len(), 1);
+    }
+
+    #[test]
+    fn should_stop_retrying_at_limit_or_deadline() {
+        let unavailable = || {
+            let transport = MockTransport::new();
+            transport.set_response("getChainInfo", Reply::text(503, ""));
+            transport
+        };
+        let client = IostClient::with_transport("http://127.0.0.1:30001", unavailable());
+        assert!(block_on(client.get_chain_info()).is_err());
+        assert_eq!(client.transport().requests().len(), 4);
+
+        let policy = RetryPolicy {
+            deadline: Some(Duration::from_millis(300)),
+            ..RetryPolicy::default()
+        };
+        let client = IostClient::with_transport("http://127.0.0.1:30001", unavailable())
+            .with_retry_policy(policy);
+        assert!(block_on(client.get_chain_info()).is_err());
+        // 200ms fits in the deadline, the following 400ms wait doesn't
+        assert_eq!(client.transport().requests().len(), 2);
+    }
+
+    #[test]
+    fn should_count_duplicate_retry_as_sent() {
+        let transport = MockTransport::new();
+        transport.set_responses(
+            "sendTx",
+            vec![
+                Reply::Fail("connection reset".to_string()),
+                Reply::error(500, 2, "tx err:DupError"),
+            ],
+        );
+        let client = IostClient::with_transport("http://127.0.0.1:30001", transport);
+        let tx = Tx::new(0, 0, 1024, vec![]);
+        let response = block_on(client.send_tx(&tx)).unwrap();
+        assert_eq!(response.hash, tx.hash().unwrap().to_string());
+        assert_eq!(client.transport().requests().len(), 2);
+    }
+
+    #[test]
+    fn should_abandon_attempt_at_deadline() {
+        let transport = MockTransport::new();
+        transport.set_responses("getChainInfo", vec![Reply::text(503, ""), Reply::Hang]);
+        let policy = RetryPolicy {
+            deadline: Some(Duration::from_secs(1)),
+            ..RetryPolicy::default()
+        };
+        let client = IostClient::with_transport("http://127.0.0.1:30001", transport)
+            .with_retry_policy(policy);
+        match block_on(client.get_chain_info()) {
+            Err(Error::RequestTimeout(deadline)) => assert_eq!(deadline, Duration::from_secs(1)),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+        // the backoff, then what was left of the deadline for the hanging attempt
+        let sleeps = client.transport().sleeps();
+        assert_eq!(sleeps.len(), 2);
+        assert_eq!(sleeps[0], Duration::from_millis(200));
+        assert!(sleeps[1] <= Duration::from_secs(1));
+        assert_eq!(client.transport().requests().len(), 2);
+    }
+}
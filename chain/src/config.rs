@@ -0,0 +1,210 @@
+//! Settings of the reqwest client behind [`ReqwestTransport`](crate::ReqwestTransport) and the
+//! blocking transport.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Proxy};
+
+use crate::{Error, Result};
+
+/// Apply a `ClientConfig` to an async or blocking `ClientBuilder`, which have the same methods.
+macro_rules! configure {
+    ($config:expr, $builder:expr) => {{
+        let config = $config;
+        let mut builder = $builder.default_headers(config.header_map()?);
+        if let Some(timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(Proxy::all(proxy.as_str()).map_err(Error::Reqwest)?);
+        }
+        for pem in &config.root_certificates {
+            builder =
+                builder.add_root_certificate(Certificate::from_pem(pem).map_err(Error::Reqwest)?);
+        }
+        if let Some(timeout) = config.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = config.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        builder.build().map_err(Error::Reqwest)
+    }};
+}
+
+/// Builder for the HTTP client of an `IostClient`. The client is built once and shared by
+/// every request, so connections are pooled.
+///
+/// ```no_run
+/// # use core::time::Duration;
+/// # use iost_chain::{ClientConfig, IostClient};
+/// let config = ClientConfig::new()
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(30))
+///     .header("x-api-key", "secret");
+/// let client = IostClient::with_config("https://api.iost.io", &config).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClientConfig {
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    headers: Vec<(String, String)>,
+    proxy: Option<String>,
+    root_certificates: Vec<Vec<u8>>,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+}
+
+impl ClientConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time allowed to establish a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Time allowed for a whole request, from connecting until the body is read. A
+    /// `subscribe` stream is one long request, so this limits how long it stays connected.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send `name: value` with every request, e.g. the API key of a hosted node.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Send all requests through the proxy at `url`, e.g. `http://10.0.0.1:3128`.
+    pub fn proxy(mut self, url: &str) -> Self {
+        self.proxy = Some(url.to_string());
+        self
+    }
+
+    /// Trust a PEM encoded root certificate, besides the system ones.
+    pub fn root_certificate(mut self, pem: &[u8]) -> Self {
+        self.root_certificates.push(pem.to_vec());
+        self
+    }
+
+    /// Close pooled connections that have been idle for `timeout`.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Keep at most `max` idle connections per host, 0 to disable pooling.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    fn header_map(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| Error::InvalidClientConfig(format!("invalid header name {}", name)))?;
+            let value = HeaderValue::from_str(value).map_err(|_| {
+                Error::InvalidClientConfig(format!("invalid value of header {}", name))
+            })?;
+            headers.append(name, value);
+        }
+        Ok(headers)
+    }
+
+    /// Build the async client.
+    pub fn build(&self) -> Result<reqwest::Client> {
+        configure!(self, reqwest::Client::builder())
+    }
+
+    /// Build the blocking client.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(&self) -> Result<reqwest::blocking::Client> {
+        configure!(self, reqwest::blocking::Client::builder())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IostClient;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Accept `count` connections, answer each request with an error after `delay`, and
+    /// send the request heads.
+    fn serve(count: usize, delay: Duration) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        let (heads, received) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming().take(count) {
+                let mut stream = stream.unwrap();
+                let mut head = Vec::new();
+                let mut byte = [0u8; 1];
+                while !head.ends_with(b"\r\n\r\n") && stream.read(&mut byte).unwrap() == 1 {
+                    head.push(byte[0]);
+                }
+                heads.send(String::from_utf8(head).unwrap()).unwrap();
+                thread::sleep(delay);
+                let _ = stream.write_all(
+                    b"HTTP/1.1 500 Internal Server Error\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}",
+                );
+            }
+        });
+        (host, received)
+    }
+
+    #[tokio::test]
+    async fn should_send_configured_headers() {
+        let (host, heads) = serve(1, Duration::from_millis(0));
+        let config = ClientConfig::new()
+            .header("x-api-key", "secret")
+            .timeout(Duration::from_secs(10));
+        let client = IostClient::with_config(&host, &config)
+            .unwrap()
+            .with_retry_policy(crate::RetryPolicy::none());
+        assert!(client.get_node_info().await.is_err());
+        let head = heads.recv().unwrap().to_lowercase();
+        assert!(head.starts_with("get /getnodeinfo "));
+        assert!(head.contains("x-api-key: secret\r\n"));
+    }
+
+    #[tokio::test]
+    async fn should_time_out() {
+        let (host, _heads) = serve(1, Duration::from_secs(2));
+        let config = ClientConfig::new().timeout(Duration::from_millis(100));
+        let client = IostClient::with_config(&host, &config)
+            .unwrap()
+            .with_retry_policy(crate::RetryPolicy::none());
+        match client.get_node_info().await {
+            Err(Error::Reqwest(err)) => assert!(err.is_timeout()),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_settings() {
+        match ClientConfig::new().header("bad header", "value").build() {
+            Err(Error::InvalidClientConfig(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(ClientConfig::new()
+            .root_certificate(b"not a pem")
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,313 @@
+//! Checks serialization, hashing and signing against vectors produced by go-iost, checked in at
+//! `vectors/go-iost.json` and generated by `vectors/gen`. Every vector carries all of its
+//! bytes, digests, signatures and hash, and the set has to cover what
+//! [`should_cover_the_encoding`] lists. The transaction signed in the IOST documentation is
+//! checked by its signature alone.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use serde::Deserialize;
+
+use crate::spv::Head;
+use crate::{SerializeData, Tx};
+
+#[derive(Deserialize)]
+struct Vectors {
+    version: u32,
+    documented: Vec<DocumentedVector>,
+    transactions: Vec<TxVector>,
+    heads: Vec<HeadVector>,
+}
+
+/// A transaction signed in the IOST documentation, with the key it was signed with, base64.
+#[derive(Deserialize)]
+struct DocumentedVector {
+    name: String,
+    source: String,
+    sec_key: String,
+    tx: Tx,
+}
+
+/// Collected by hand in `vectors/mainnet.json`, which the generator doesn't touch.
+#[derive(Deserialize)]
+struct Mainnet {
+    transactions: Vec<MainnetVector>,
+}
+
+#[derive(Deserialize)]
+struct TxVector {
+    name: String,
+    /// the key of the publisher signature, base64
+    sec_key: String,
+    /// the key of each signer signature, in order, base64
+    signer_keys: Vec<String>,
+    tx: Tx,
+    /// base64, empty unless the transaction is deferred; not part of the JSON of `tx`
+    #[serde(default)]
+    referred_tx: String,
+    bytes: String,
+    signed_bytes: String,
+    digest: String,
+    signed_digest: String,
+    hash: String,
+}
+
+impl TxVector {
+    fn tx(&self) -> Tx {
+        let mut tx = self.tx.clone();
+        tx.referred_tx = base64::decode(&self.referred_tx).unwrap();
+        tx
+    }
+}
+
+#[derive(Deserialize)]
+struct HeadVector {
+    name: String,
+    head: Head,
+    bytes: String,
+    hash: String,
+}
+
+/// A transaction accepted by the mainnet, as it was sent, with the hash getTxByHash finds it
+/// under. The node's answer leaves out the publisher signatures, so it can't stand in for `tx`.
+#[derive(Deserialize)]
+struct MainnetVector {
+    name: String,
+    tx: Tx,
+    /// base64, empty unless the transaction is deferred
+    #[serde(default)]
+    referred_tx: String,
+    hash: String,
+}
+
+fn vectors() -> Vectors {
+    let vectors: Vectors = serde_json::from_str(include_str!("../vectors/go-iost.json")).unwrap();
+    assert_eq!(vectors.version, 2);
+    vectors
+}
+
+fn mainnet() -> Vec<MainnetVector> {
+    let mainnet: Mainnet = serde_json::from_str(include_str!("../vectors/mainnet.json")).unwrap();
+    mainnet.transactions
+}
+
+fn check(name: &str, what: &str, expected: &str, actual: &[u8]) {
+    assert_eq!(
+        base64::encode(actual),
+        expected,
+        "{} of {} differs from go-iost",
+        what,
+        name
+    );
+}
+
+#[test]
+fn should_match_go_iost_transactions() {
+    for vector in &vectors().transactions {
+        let tx = vector.tx();
+        let name = &vector.name;
+        let bytes = tx.customized_to_serialize_data(false).unwrap();
+        let signed_bytes = tx.customized_to_serialize_data(true).unwrap();
+        check(name, "bytes", &vector.bytes, &bytes);
+        check(name, "signed bytes", &vector.signed_bytes, &signed_bytes);
+        check(name, "digest", &vector.digest, &tx.digest(false).unwrap());
+        check(
+            name,
+            "signed digest",
+            &vector.signed_digest,
+            &tx.digest(true).unwrap(),
+        );
+        assert_eq!(
+            tx.hash().unwrap().to_string(),
+            vector.hash,
+            "hash of {}",
+            name
+        );
+        // the binary form decodes back to the same transaction
+        for (with_sign, bytes) in [(false, &bytes), (true, &signed_bytes)].iter() {
+            let decoded = Tx::decode(bytes, *with_sign).unwrap();
+            assert_eq!(
+                &decoded.customized_to_serialize_data(*with_sign).unwrap(),
+                *bytes,
+                "{} doesn't round trip",
+                name
+            );
+        }
+
+        // a deferred transaction isn't signed, see `should_defer_like_go_iost`
+        if tx.is_deferred() {
+            continue;
+        }
+        assert!(!tx.publisher_sigs.is_empty(), "{} isn't signed", name);
+        if let Err(e) = tx.verify() {
+            panic!("signatures of {} don't verify: {}", name, e);
+        }
+
+        resign(name, &tx, &vector.sec_key, &vector.signer_keys);
+    }
+}
+
+/// Sign `tx` again with the keys of its signatures, base64, and check that it gives the same
+/// ed25519 signatures, which are deterministic, and secp256k1 ones that verify.
+fn resign(name: &str, tx: &Tx, sec_key: &str, signer_keys: &[String]) {
+    assert_eq!(
+        signer_keys.len(),
+        tx.signatures.len(),
+        "signer keys of {}",
+        name
+    );
+    let mut resigned = tx.clone();
+    resigned.signatures.clear();
+    resigned.publisher_sigs.clear();
+    for ((signer, signature), key) in tx.signers.iter().zip(&tx.signatures).zip(signer_keys) {
+        resigned
+            .sign_as_signer(signer, &signature.algorithm, &base64::decode(key).unwrap())
+            .unwrap();
+    }
+    let algorithm = &tx.publisher_sigs[0].algorithm;
+    resigned
+        .sign(
+            tx.publisher.clone(),
+            algorithm,
+            &base64::decode(sec_key).unwrap(),
+        )
+        .unwrap();
+    assert!(
+        resigned.verify().is_ok(),
+        "{} doesn't verify re-signed",
+        name
+    );
+    for (ours, theirs) in resigned
+        .signatures
+        .iter()
+        .chain(&resigned.publisher_sigs)
+        .zip(tx.signatures.iter().chain(&tx.publisher_sigs))
+    {
+        assert_eq!(ours.public_key, theirs.public_key, "key of {}", name);
+        if ours.algorithm == keys::algorithm::ED25519 {
+            assert_eq!(ours.signature, theirs.signature, "signature of {}", name);
+        }
+    }
+}
+
+#[test]
+fn should_match_documented_transactions() {
+    let documented = vectors().documented;
+    assert!(!documented.is_empty());
+    for vector in &documented {
+        let name = &vector.name;
+        if let Err(e) = vector.tx.verify() {
+            panic!(
+                "signatures of {} from {} don't verify: {}",
+                name, vector.source, e
+            );
+        }
+        resign(name, &vector.tx, &vector.sec_key, &[]);
+    }
+}
+
+#[test]
+fn should_defer_like_go_iost() {
+    let vectors = vectors().transactions;
+    for deferred in vectors.iter().filter(|v| !v.referred_tx.is_empty()) {
+        let name = &deferred.name;
+        let referred_tx = base64::decode(&deferred.referred_tx).unwrap();
+        let referred = bs58::encode(&referred_tx).into_string();
+        let delayed = match vectors.iter().find(|v| v.hash == referred) {
+            Some(delayed) => delayed.tx(),
+            None => panic!("{} refers to no vector", name),
+        };
+        let ours = delayed.deferred().unwrap();
+        assert_eq!(ours.referred_tx, referred_tx, "referred tx of {}", name);
+        check(
+            name,
+            "signed bytes",
+            &deferred.signed_bytes,
+            &ours.customized_to_serialize_data(true).unwrap(),
+        );
+        assert_eq!(
+            delayed.deferred_hash().unwrap().to_string(),
+            deferred.hash,
+            "hash of {}",
+            name
+        );
+    }
+}
+
+#[test]
+fn should_match_go_iost_heads() {
+    for vector in &vectors().heads {
+        check(
+            &vector.name,
+            "bytes",
+            &vector.bytes,
+            &vector.head.to_serialize_data().unwrap(),
+        );
+        assert_eq!(
+            bs58::encode(vector.head.hash()).into_string(),
+            vector.hash,
+            "hash of {}",
+            vector.name
+        );
+    }
+}
+
+#[test]
+fn should_match_mainnet_hashes() {
+    let mainnet = mainnet();
+    assert!(!mainnet.is_empty(), "no mainnet transaction");
+    assert!(
+        mainnet.iter().any(|v| !v.referred_tx.is_empty()),
+        "no deferred mainnet transaction"
+    );
+    for vector in &mainnet {
+        let mut tx = vector.tx.clone();
+        tx.referred_tx = base64::decode(&vector.referred_tx).unwrap();
+        assert_eq!(
+            tx.hash().unwrap().to_string(),
+            vector.hash,
+            "hash of {}",
+            vector.name
+        );
+    }
+}
+
+/// The vectors exercise every part of the encoding.
+#[test]
+fn should_cover_the_encoding() {
+    let vectors = vectors();
+    let txs: Vec<Tx> = vectors.transactions.iter().map(TxVector::tx).collect();
+    let any = |what: &str, covered: &dyn Fn(&Tx) -> bool| {
+        assert!(txs.iter().any(covered), "no vector with {}", what);
+    };
+    let signed_with = |algorithm: &'static str| {
+        move |tx: &Tx| {
+            tx.signatures
+                .iter()
+                .chain(&tx.publisher_sigs)
+                .any(|signature| signature.algorithm == algorithm)
+        }
+    };
+    any("ed25519 signatures", &signed_with(keys::algorithm::ED25519));
+    any(
+        "secp256k1 signatures",
+        &signed_with(keys::algorithm::SECP256K1),
+    );
+    any("no signers", &|tx| tx.signers.is_empty());
+    any("several signers", &|tx| tx.signers.len() > 1);
+    any("no actions", &|tx| tx.actions.is_empty());
+    any("several actions", &|tx| tx.actions.len() > 1);
+    any("no amount limits", &|tx| tx.amount_limit.is_empty());
+    any("several amount limits", &|tx| tx.amount_limit.len() > 1);
+    any("a delay", &|tx| tx.delay > 0);
+    any("a referred transaction", &|tx| tx.is_deferred());
+    assert!(
+        vectors.heads.iter().any(|v| v.head.info.is_empty()),
+        "no head without info"
+    );
+    assert!(
+        vectors.heads.iter().any(|v| !v.head.info.is_empty()),
+        "no head with info"
+    );
+}
@@ -0,0 +1,41 @@
+//! Lenient deserializers for the node API.
+//!
+//! The gateway in front of the node encodes 64-bit integers as JSON strings,
+//! while hand-written fixtures and older nodes send plain numbers.
+#![cfg(feature = "std")]
+
+use serde::{Deserialize, Deserializer};
+
+pub(crate) fn value_to_i64(value: &serde_json::Value) -> Option<i64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f as i64)),
+        serde_json::Value::String(s) => s.parse::<i64>().ok(),
+        _ => None,
+    }
+}
+
+pub(crate) fn value_to_f64(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+pub fn de_number_or_string_to_i64<'de, D>(de: D) -> Result<i64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(de)?;
+    value_to_i64(&value)
+        .ok_or_else(|| serde::de::Error::custom(alloc::format!("invalid integer: {}", value)))
+}
+
+pub fn de_number_or_string_to_f64<'de, D>(de: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(de)?;
+    value_to_f64(&value)
+        .ok_or_else(|| serde::de::Error::custom(alloc::format!("invalid number: {}", value)))
+}
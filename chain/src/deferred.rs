@@ -0,0 +1,144 @@
+//! Delayed transactions. A transaction published with a `delay` is packed like any other, and
+//! the node then creates a deferred transaction from it that executes `delay` later, unless the
+//! publisher revokes it first with [`IostAction::cancel_delay_tx`].
+
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use lite_json::{JsonValue, Serialize};
+
+use crate::{Error, IostAction, Result, Tx, TxHash};
+
+impl Tx {
+    /// Whether this is a deferred transaction, created by the node from a delayed one.
+    pub fn is_deferred(&self) -> bool {
+        !self.referred_tx.is_empty()
+    }
+
+    /// The deferred transaction the node creates from this delayed transaction, like go-iost's
+    /// `Tx.DeferTx`: the same actions, signers, limits and publisher moved `delay` later,
+    /// without a delay of its own or any signatures, referring to this one. Fails with
+    /// `Error::InvalidTx` unless the delay is positive.
+    pub fn deferred(&self) -> Result<Tx> {
+        if self.delay <= 0 {
+            return Err(Error::InvalidTx("no delay".to_string()));
+        }
+        if self.is_deferred() {
+            return Err(Error::InvalidTx("already deferred".to_string()));
+        }
+        let shift = |at: i64| {
+            at.checked_add(self.delay)
+                .ok_or_else(|| Error::InvalidTx("delay overflows the expiration".to_string()))
+        };
+        Ok(Tx {
+            time: shift(self.time)?,
+            expiration: shift(self.expiration)?,
+            gas_ratio: self.gas_ratio,
+            gas_limit: self.gas_limit,
+            delay: 0,
+            chain_id: self.chain_id,
+            actions: self.actions.clone(),
+            amount_limit: self.amount_limit.clone(),
+            publisher: self.publisher.clone(),
+            publisher_sigs: Vec::new(),
+            signers: self.signers.clone(),
+            signatures: Vec::new(),
+            referred_tx: self.hash()?.as_bytes().to_vec(),
+        })
+    }
+
+    /// Hash of [`Tx::deferred`], under which the node reports the execution of the delayed
+    /// transaction. Look up the transaction itself, e.g. to cancel it, by [`Tx::hash`].
+    pub fn deferred_hash(&self) -> Result<TxHash> {
+        self.deferred()?.hash()
+    }
+}
+
+impl IostAction {
+    /// `system.iost` `cancelDelaytx`: revoke the delayed transaction `hash`, as returned by
+    /// `sendTx`, before it executes. Only its publisher can cancel it.
+    pub fn cancel_delay_tx(hash: &TxHash) -> IostAction {
+        let data = JsonValue::Array(vec![JsonValue::String(hash.to_string().chars().collect())]);
+        IostAction {
+            contract: b"system.iost".to_vec(),
+            action_name: b"cancelDelaytx".to_vec(),
+            data: data.serialize(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{NumberBytes, Read, SerializeData};
+    use keys::algorithm;
+
+    fn delayed() -> Tx {
+        let sec_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
+        let mut tx = Tx::builder()
+            .action(IostAction::transfer("treasury", "bob", "10", "payout").unwrap())
+            .time(1_000)
+            .expiration(2_000)
+            .delay(core::time::Duration::from_nanos(500))
+            .build()
+            .unwrap();
+        tx.sign("treasury".to_string(), algorithm::ED25519, &sec_key)
+            .unwrap();
+        tx
+    }
+
+    #[test]
+    fn should_derive_deferred_tx() {
+        let tx = delayed();
+        let deferred = tx.deferred().unwrap();
+        assert!(!tx.is_deferred());
+        assert!(deferred.is_deferred());
+        assert_eq!(deferred.time, 1_500);
+        assert_eq!(deferred.expiration, 2_500);
+        assert_eq!(deferred.delay, 0);
+        assert_eq!(deferred.publisher, "treasury");
+        assert_eq!(deferred.actions.len(), 1);
+        assert_eq!(deferred.gas_limit, tx.gas_limit);
+        // the node creates it, nobody signs it
+        assert!(deferred.publisher_sigs.is_empty() && deferred.signatures.is_empty());
+        assert_eq!(deferred.referred_tx, tx.hash().unwrap().as_bytes().to_vec());
+        assert_eq!(tx.deferred_hash().unwrap(), deferred.hash().unwrap());
+        assert_ne!(tx.deferred_hash().unwrap(), tx.hash().unwrap());
+
+        assert!(matches!(deferred.deferred(), Err(Error::InvalidTx(_))));
+        let mut now = tx.clone();
+        now.delay = 0;
+        assert!(matches!(now.deferred(), Err(Error::InvalidTx(_))));
+        now.delay = i64::MAX;
+        assert!(matches!(now.deferred(), Err(Error::InvalidTx(_))));
+    }
+
+    #[test]
+    fn should_keep_referred_tx_out_of_the_layout() {
+        let tx = delayed();
+        let deferred = tx.deferred().unwrap();
+        let mut plain = deferred.clone();
+        plain.referred_tx.clear();
+        assert_eq!(deferred.num_bytes(), plain.num_bytes());
+        let bytes = deferred.to_serialize_data().unwrap();
+        assert_eq!(bytes, plain.to_serialize_data().unwrap());
+
+        let read = Tx::read(&bytes, &mut 0).unwrap();
+        assert!(read.referred_tx.is_empty());
+        assert_eq!(read.time, deferred.time);
+        assert_eq!(read.publisher, deferred.publisher);
+    }
+
+    #[test]
+    fn should_build_cancel_delay_tx() {
+        let hash = delayed().hash().unwrap();
+        let action = IostAction::cancel_delay_tx(&hash);
+        assert_eq!(action.contract, b"system.iost".to_vec());
+        assert_eq!(action.action_name, b"cancelDelaytx".to_vec());
+        assert_eq!(
+            String::from_utf8(action.data).unwrap(),
+            alloc::format!("[\"{}\"]", hash)
+        );
+    }
+}
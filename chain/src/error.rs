@@ -1,5 +1,9 @@
-use crate::{ErrorMessage, ParseNameError, ReadError, WriteError};
+use crate::{
+    ErrorMessage, NodeErrorKind, ParseNameError, ReadError, StatusCode, TxReceipt, WriteError,
+};
+use alloc::boxed::Box;
 use alloc::string::String;
+use core::fmt;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -9,8 +13,33 @@ pub enum Error {
     BytesWriteError(WriteError),
 
     JsonParserError(),
+    ///Error request message
+    #[cfg(feature = "client")]
+    Reqwest(reqwest::Error),
+    ///Error connecting to the gRPC API of a node
+    #[cfg(feature = "grpc")]
+    GrpcTransport(tonic::transport::Error),
+    ///Error reported by an HttpTransport
+    HttpTransportError(String),
+    ///The HttpTransport can't stream responses
+    StreamingUnsupported(),
+    ///The request didn't finish within the deadline of the retry policy
+    RequestTimeout(core::time::Duration),
+    ///Invalid setting of a ClientConfig
+    #[cfg(feature = "client")]
+    InvalidClientConfig(String),
+    ///The on-disk store of a cache can't be used
+    CacheError(String),
     ///Error response message
     ErrorMessage(ErrorMessage),
+    ///Error response of a node, classified
+    NodeError(NodeErrorKind, ErrorMessage),
+    ///The transaction was packed, but its execution failed
+    TxFailed(Box<TxReceipt>),
+    ///The head block passed the expiration of the transaction before it was packed
+    TxExpired(String),
+    ///The transaction wasn't confirmed within the tracker's timeout
+    TxConfirmTimeout(String),
 
     ParseNameErr(ParseNameError),
 
@@ -20,7 +49,21 @@ pub enum Error {
     FixedParseDivideByZero(),
     FixedParseDoubleDot(),
 
+    ///The transaction breaks a rule of the chain
+    InvalidTx(String),
     InvalidSignature(),
+    ///The signature algorithm isn't ED25519 or SECP256K1
+    UnsupportedAlgorithm(String),
+    ///Invalid key
+    KeyError(keys::error::Error),
+    ///Bytes that aren't an encoded transaction
+    TxDecodeError(String),
+    ///Not the base58 encoding of a 32 byte hash
+    InvalidTxHash(String),
+    ///A signer of the transaction hasn't signed it
+    MissingSignerSignature(),
+    ///Two signatures of signers are by the same key, base64
+    DuplicateSignerKey(String),
     InvalidPublisherSignature(),
 
     InvalidSPVStartBlock(i64),
@@ -31,3 +74,96 @@ pub enum Error {
     IOSTUpdateEpochError(String),
     IOSTBlockWitnessError(String),
 }
+
+impl Error {
+    /// What went wrong on the node, for errors reported by a node. Match on this instead of
+    /// the message text.
+    pub fn node_error_kind(&self) -> Option<NodeErrorKind> {
+        match self {
+            Error::NodeError(kind, _) => Some(*kind),
+            Error::ErrorMessage(message) => Some(NodeErrorKind::classify(0, message)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::BytesReadError(ReadError::NotEnoughBytes) => f.write_str("not enough bytes"),
+            Error::BytesReadError(ReadError::NotSupportMessageType) => {
+                f.write_str("unsupported message type")
+            }
+            Error::BytesWriteError(WriteError::NotEnoughSpace) => f.write_str("not enough space"),
+            Error::BytesWriteError(WriteError::TryFromIntError) => {
+                f.write_str("integer out of range")
+            }
+            Error::JsonParserError() => f.write_str("invalid JSON"),
+            #[cfg(feature = "client")]
+            Error::Reqwest(err) => write!(f, "request failed: {}", err),
+            #[cfg(feature = "grpc")]
+            Error::GrpcTransport(err) => write!(f, "gRPC connection failed: {}", err),
+            Error::HttpTransportError(err) => write!(f, "request failed: {}", err),
+            Error::StreamingUnsupported() => f.write_str("the transport can't stream responses"),
+            Error::RequestTimeout(deadline) => write!(f, "request timed out after {:?}", deadline),
+            #[cfg(feature = "client")]
+            Error::InvalidClientConfig(err) => write!(f, "invalid client config: {}", err),
+            Error::CacheError(err) => write!(f, "cache store failed: {}", err),
+            Error::ErrorMessage(message) => {
+                write!(f, "node error {}: {}", message.code, message.message)
+            }
+            Error::NodeError(kind, message) => write!(
+                f,
+                "node error ({}) {}: {}",
+                kind, message.code, message.message
+            ),
+            Error::TxFailed(receipt) => write!(
+                f,
+                "transaction {} failed with {:?}: {}",
+                receipt.tx_hash,
+                StatusCode::from_code(receipt.status.code),
+                receipt.status.message
+            ),
+            Error::TxExpired(hash) => write!(f, "transaction {} expired", hash),
+            Error::TxConfirmTimeout(hash) => {
+                write!(f, "timed out waiting for transaction {}", hash)
+            }
+            Error::ParseNameErr(err) => fmt::Display::fmt(err, f),
+            Error::FixedParseOverflow() => f.write_str("amount overflows"),
+            Error::FixedParseAbnormalChar() => f.write_str("amount contains an invalid character"),
+            Error::FixedParseAmountFormat() => f.write_str("invalid amount format"),
+            Error::FixedParseDivideByZero() => f.write_str("amount divided by zero"),
+            Error::FixedParseDoubleDot() => f.write_str("amount contains two dots"),
+            Error::InvalidTx(rule) => write!(f, "invalid transaction: {}", rule),
+            Error::InvalidSignature() => f.write_str("invalid signature"),
+            Error::UnsupportedAlgorithm(algorithm) => {
+                write!(f, "unsupported signature algorithm {}", algorithm)
+            }
+            Error::KeyError(err) => write!(f, "invalid key: {}", err),
+            Error::TxDecodeError(reason) => write!(f, "invalid transaction bytes: {}", reason),
+            Error::InvalidTxHash(hash) => write!(f, "invalid transaction hash {}", hash),
+            Error::MissingSignerSignature() => f.write_str("missing signature of a signer"),
+            Error::DuplicateSignerKey(key) => write!(f, "two signer signatures by key {}", key),
+            Error::InvalidPublisherSignature() => f.write_str("invalid publisher signature"),
+            Error::InvalidSPVStartBlock(number) => write!(f, "invalid SPV start block {}", number),
+            Error::IOSTBlockError() => f.write_str("invalid block"),
+            Error::IOSTBlockVerifyError(err) => write!(f, "block verification failed: {}", err),
+            Error::IOSTInvalidBlockSignature() => f.write_str("invalid block signature"),
+            Error::IOSTUpdateEpochError(err) => write!(f, "epoch update failed: {}", err),
+            Error::IOSTBlockWitnessError(err) => write!(f, "invalid witness: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "client")]
+            Error::Reqwest(err) => Some(err),
+            #[cfg(feature = "grpc")]
+            Error::GrpcTransport(err) => Some(err),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Gas and RAM estimates from a dry run of a transaction, see [`IostClient::estimate_tx`].
+
+use alloc::boxed::Box;
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+
+use crate::{Error, Result, StatusCode, Tx, TxReceipt};
+
+/// lowest gas limit a node accepts, and so the lowest `gas_limit` the estimator suggests
+pub const MIN_GAS_LIMIT: f64 = 50_000.0;
+/// factor applied to the gas used by the dry run
+pub const DEFAULT_GAS_MARGIN: f64 = 1.2;
+
+/// Costs of a transaction, from the receipt of `execTx`.
+#[derive(Debug, Clone)]
+pub struct Estimate {
+    /// gas used by the dry run
+    pub gas_usage: f64,
+    /// suggested `Tx.gas_limit`: the gas used times the margin, at least [`MIN_GAS_LIMIT`]
+    pub gas_limit: f64,
+    /// RAM in bytes each account is charged for; negative for released RAM
+    pub ram_usage: BTreeMap<String, i64>,
+    pub receipt: TxReceipt,
+}
+
+impl Estimate {
+    /// Estimate from a dry run receipt. Fails with `Error::TxFailed` if the dry run failed, as
+    /// the transaction would fail on chain too.
+    pub fn from_receipt(receipt: TxReceipt, margin: f64) -> Result<Self> {
+        if receipt.status.code != StatusCode::SUCCESS.code() {
+            return Err(Error::TxFailed(Box::new(receipt)));
+        }
+        let gas_limit = (receipt.gas_usage * margin).ceil().max(MIN_GAS_LIMIT);
+        Ok(Estimate {
+            gas_usage: receipt.gas_usage,
+            gas_limit,
+            ram_usage: receipt.ram_usage.clone(),
+            receipt,
+        })
+    }
+
+    /// RAM charged to `account`, 0 if it isn't charged.
+    pub fn ram_usage_of(&self, account: &str) -> i64 {
+        self.ram_usage.get(account).copied().unwrap_or_default()
+    }
+
+    /// Set the suggested gas limit on `tx`. Do it before signing, since the limit is signed.
+    pub fn apply(&self, tx: &mut Tx) {
+        tx.gas_limit = self.gas_limit;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::IostClient;
+    use crate::mock::{fixtures, MockTransport, Reply};
+    use crate::transport::block_on;
+    use crate::IostAction;
+    use alloc::vec;
+
+    /// Estimate a transfer, on a node answering `execTx` with `receipt`, or with the fixture
+    /// receipt if `None`.
+    fn estimate(receipt: Option<serde_json::Value>) -> Result<Estimate> {
+        let transport = MockTransport::new();
+        if let Some(receipt) = receipt {
+            transport.set_response("execTx", Reply::ok(receipt));
+        }
+        let client = IostClient::with_transport("http://127.0.0.1:30001", transport);
+        let action = IostAction::transfer("admin", "lispczz3", "10", "").unwrap();
+        let tx = Tx::from_action(vec![action]);
+        let estimate = block_on(client.estimate_tx(&tx, DEFAULT_GAS_MARGIN));
+        assert_eq!(client.transport().count("execTx"), 1);
+        estimate
+    }
+
+    #[test]
+    fn should_estimate_gas_and_ram() {
+        let mut receipt = fixtures::tx_receipt("abc");
+        receipt["gas_usage"] = 250000.into();
+        receipt["ram_usage"] = serde_json::json!({"admin": 196, "lispczz3": -20});
+        let estimate = estimate(Some(receipt)).unwrap();
+        assert_eq!(estimate.gas_usage, 250000.0);
+        assert_eq!(estimate.gas_limit, 300000.0);
+        assert_eq!(estimate.ram_usage_of("admin"), 196);
+        assert_eq!(estimate.ram_usage_of("lispczz3"), -20);
+        assert_eq!(estimate.ram_usage_of("nobody"), 0);
+
+        let mut tx = Tx::from_action(vec![]);
+        estimate.apply(&mut tx);
+        assert_eq!(tx.gas_limit, 300000.0);
+    }
+
+    #[test]
+    fn should_suggest_min_gas_limit() {
+        // the fixture receipt uses 2577 gas
+        let estimate = estimate(None).unwrap();
+        assert_eq!(estimate.gas_usage, 2577.0);
+        assert_eq!(estimate.gas_limit, MIN_GAS_LIMIT);
+    }
+
+    #[test]
+    fn should_report_runtime_error() {
+        let mut receipt = fixtures::tx_receipt("abc");
+        receipt["status_code"] = "RUNTIME_ERROR".into();
+        receipt["message"] = "invalid account lispczz3".into();
+        match estimate(Some(receipt)) {
+            Err(Error::TxFailed(receipt)) => {
+                assert_eq!(receipt.status.code, StatusCode::RUNTIME_ERROR.code());
+                assert_eq!(receipt.status.message, "invalid account lispczz3");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
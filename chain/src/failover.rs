@@ -0,0 +1,568 @@
+//! Client over several IOST nodes that fails over between them.
+//!
+//! Node health is checked by querying `getChainInfo` on every node at once, every
+//! `check_interval` in the background when [`FailoverClient::refresh_health`] runs (see
+//! `spawn_health_checks`), and otherwise by the first call after `check_interval` has passed.
+//! Nodes that fail the check, don't answer within `probe_timeout`, or whose head block lags the
+//! best node by more than `max_lag`, are skipped until the next check.
+
+use alloc::string::{String, ToString};
+use alloc::sync::{Arc, Weak};
+use alloc::vec::Vec;
+use core::future::Future;
+use futures_util::future::join_all;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::node_error::is_transient;
+#[cfg(feature = "client")]
+use crate::transport::ReqwestTransport;
+use crate::transport::{timeout, HttpTransport};
+#[cfg(feature = "client")]
+use crate::RetryPolicy;
+use crate::{
+    Account, BatchContractStorage, BatchContractStoragePost, BlockByHash, BlockByNumber,
+    CandidateBonus, ChainInfo, Contract, ContractStorage, ContractStorageFields,
+    ContractStorageFieldsPost, ContractStoragePost, Error, GasRatio, GetTxByHash, IostClient,
+    NodeInfo, ProducerVoteInfo, RamInfo, Result, TokenBalance, TokenInfo, Tx, TxReceipt,
+    TxResponse, VoterBonus,
+};
+
+#[derive(Debug, Clone)]
+pub struct FailoverConfig {
+    /// how long health check results are trusted
+    pub check_interval: Duration,
+    /// how long the health check waits for a node's answer before counting it as down
+    pub probe_timeout: Duration,
+    /// nodes whose head block is more than this many blocks behind the best node are skipped
+    pub max_lag: i64,
+    /// number of nodes `send_tx` publishes to
+    pub send_fan_out: usize,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        FailoverConfig {
+            check_interval: Duration::from_secs(30),
+            probe_timeout: Duration::from_secs(5),
+            max_lag: 30,
+            send_fan_out: 1,
+        }
+    }
+}
+
+/// health of one node, as seen by the last check
+#[derive(Debug, Clone)]
+pub struct NodeStatus {
+    pub host: String,
+    pub healthy: bool,
+    pub head_block: i64,
+}
+
+struct Node<T> {
+    client: Arc<IostClient<T>>,
+    status: Mutex<NodeStatus>,
+}
+
+pub struct FailoverClient<T> {
+    nodes: Vec<Node<T>>,
+    config: FailoverConfig,
+    last_check: Mutex<Option<Instant>>,
+}
+
+#[cfg(feature = "client")]
+impl FailoverClient<ReqwestTransport> {
+    /// Nodes don't retry on their own, a failed request moves on to the next node.
+    pub fn new(hosts: &[&str], config: FailoverConfig) -> Self {
+        let transport = ReqwestTransport::new();
+        let clients = hosts
+            .iter()
+            .map(|host| {
+                IostClient::with_transport(host, transport.clone())
+                    .with_retry_policy(RetryPolicy::none())
+            })
+            .collect();
+        Self::with_clients(clients, config)
+    }
+}
+
+#[cfg(feature = "client")]
+impl<T: HttpTransport + 'static> FailoverClient<T> {
+    /// Run [`FailoverClient::refresh_health`] on the tokio runtime.
+    pub fn spawn_health_checks(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(Self::refresh_health(Arc::downgrade(self)))
+    }
+}
+
+/// Whether an error says something about the node rather than about the request.
+fn is_node_failure(err: &Error) -> bool {
+    match err {
+        Error::JsonParserError() | Error::RequestTimeout(_) => true,
+        err => is_transient(err),
+    }
+}
+
+impl<T: HttpTransport> FailoverClient<T> {
+    pub fn with_clients(clients: Vec<IostClient<T>>, config: FailoverConfig) -> Self {
+        let nodes = clients
+            .into_iter()
+            .map(|client| Node {
+                status: Mutex::new(NodeStatus {
+                    host: client.host().to_string(),
+                    healthy: true,
+                    head_block: 0,
+                }),
+                client: Arc::new(client),
+            })
+            .collect();
+        FailoverClient {
+            nodes,
+            config,
+            last_check: Mutex::new(None),
+        }
+    }
+
+    pub fn config(&self) -> &FailoverConfig {
+        &self.config
+    }
+
+    /// Status of every node, in the order they were given.
+    pub fn node_status(&self) -> Vec<NodeStatus> {
+        self.nodes
+            .iter()
+            .map(|node| node.status.lock().unwrap().clone())
+            .collect()
+    }
+
+    /// Query every node now, regardless of `check_interval`. The nodes are probed at once,
+    /// each for at most `probe_timeout`.
+    pub async fn check_health(&self) {
+        let probes = self.nodes.iter().map(|node| async move {
+            let client = &node.client;
+            let info = timeout(
+                client.transport(),
+                self.config.probe_timeout,
+                client.get_chain_info(),
+            )
+            .await;
+            let head_block = match info {
+                Some(Ok(info)) => info.head_block.parse::<i64>().ok(),
+                _ => None,
+            };
+            let mut status = node.status.lock().unwrap();
+            status.healthy = head_block.is_some();
+            status.head_block = head_block.unwrap_or(status.head_block);
+        });
+        join_all(probes).await;
+        *self.last_check.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Check every node each `check_interval` until the client is dropped, so that nodes are
+    /// re-checked while no calls arrive. The client is only held while a check runs.
+    pub async fn refresh_health(client: Weak<Self>) {
+        loop {
+            let (node, interval) = match client.upgrade() {
+                Some(client) => match client.nodes.first() {
+                    Some(node) => {
+                        client.check_health().await;
+                        (node.client.clone(), client.config.check_interval)
+                    }
+                    None => return,
+                },
+                None => return,
+            };
+            node.transport().sleep(interval).await;
+        }
+    }
+
+    async fn check_health_if_due(&self) {
+        let due = match *self.last_check.lock().unwrap() {
+            Some(last_check) => last_check.elapsed() >= self.config.check_interval,
+            None => true,
+        };
+        if due {
+            self.check_health().await;
+        }
+    }
+
+    /// Indices of usable nodes, best first. Falls back to every node when none is healthy.
+    fn ranked(&self) -> Vec<usize> {
+        let statuses = self.node_status();
+        let best = statuses
+            .iter()
+            .filter(|status| status.healthy)
+            .map(|status| status.head_block)
+            .max();
+        let mut ranked: Vec<usize> = match best {
+            Some(best) => (0..statuses.len())
+                .filter(|&i| {
+                    statuses[i].healthy && best - statuses[i].head_block <= self.config.max_lag
+                })
+                .collect(),
+            None => (0..statuses.len()).collect(),
+        };
+        // stable sort keeps the given order among nodes at the same height
+        ranked.sort_by(|&a, &b| statuses[b].head_block.cmp(&statuses[a].head_block));
+        ranked
+    }
+
+    fn mark_unhealthy(&self, index: usize) {
+        self.nodes[index].status.lock().unwrap().healthy = false;
+    }
+
+    /// Run a read on the best node, failing over to the next one when the node itself fails.
+    pub async fn read<F, Fut, R>(&self, f: F) -> Result<R>
+    where
+        F: Fn(Arc<IostClient<T>>) -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
+        self.check_health_if_due().await;
+        let mut last_err = None;
+        for index in self.ranked() {
+            match f(self.nodes[index].client.clone()).await {
+                Err(err) if is_node_failure(&err) => {
+                    self.mark_unhealthy(index);
+                    last_err = Some(err);
+                }
+                result => return result,
+            }
+        }
+        Err(last_err.unwrap_or_else(|| Error::HttpTransportError("no IOST node".to_string())))
+    }
+
+    /// Publish a signed transaction to the best `send_fan_out` nodes at once, and return the
+    /// first successful response. If all of them fail, the next `send_fan_out` nodes are
+    /// tried. A rejection of the transaction itself, rather than a node failure, is returned
+    /// without trying other nodes.
+    pub async fn send_tx(&self, tx: &Tx) -> Result<TxResponse> {
+        self.check_health_if_due().await;
+        let ranked = self.ranked();
+        let mut last_err = None;
+        for batch in ranked.chunks(self.config.send_fan_out.max(1)) {
+            let sends = batch
+                .iter()
+                .map(|&index| async move { (index, self.nodes[index].client.send_tx(tx).await) });
+            let mut response = None;
+            let mut rejection = None;
+            for (index, result) in join_all(sends).await {
+                match result {
+                    Ok(rsp) => response = response.or(Some(rsp)),
+                    Err(err) if is_node_failure(&err) => {
+                        self.mark_unhealthy(index);
+                        last_err = Some(err);
+                    }
+                    Err(err) => rejection = rejection.or(Some(err)),
+                }
+            }
+            if let Some(rsp) = response {
+                return Ok(rsp);
+            }
+            if let Some(err) = rejection {
+                return Err(err);
+            }
+        }
+        Err(last_err.unwrap_or_else(|| Error::HttpTransportError("no IOST node".to_string())))
+    }
+
+    pub async fn exec_tx(&self, tx: &Tx) -> Result<TxReceipt> {
+        self.read(|c| async move { c.exec_tx(tx).await }).await
+    }
+
+    pub async fn get_node_info(&self) -> Result<NodeInfo> {
+        self.read(|c| async move { c.get_node_info().await }).await
+    }
+
+    pub async fn get_chain_info(&self) -> Result<ChainInfo> {
+        self.read(|c| async move { c.get_chain_info().await }).await
+    }
+
+    pub async fn get_gas_ratio(&self) -> Result<GasRatio> {
+        self.read(|c| async move { c.get_gas_ratio().await }).await
+    }
+
+    pub async fn get_ram_info(&self) -> Result<RamInfo> {
+        self.read(|c| async move { c.get_ram_info().await }).await
+    }
+
+    pub async fn get_tx_by_hash(&self, hash: &str) -> Result<GetTxByHash> {
+        self.read(|c| async move { c.get_tx_by_hash(hash).await })
+            .await
+    }
+
+    pub async fn get_tx_receipt_by_tx_hash(&self, hash: &str) -> Result<TxReceipt> {
+        self.read(|c| async move { c.get_tx_receipt_by_tx_hash(hash).await })
+            .await
+    }
+
+    pub async fn get_block_by_hash(&self, hash: &str, complete: bool) -> Result<BlockByHash> {
+        self.read(|c| async move { c.get_block_by_hash(hash, complete).await })
+            .await
+    }
+
+    pub async fn get_block_by_number(&self, number: i64, complete: bool) -> Result<BlockByNumber> {
+        self.read(|c| async move { c.get_block_by_number(number, complete).await })
+            .await
+    }
+
+    pub async fn get_account(&self, name: &str, by_longest_chain: bool) -> Result<Account> {
+        self.read(|c| async move { c.get_account(name, by_longest_chain).await })
+            .await
+    }
+
+    pub async fn get_token_balance(
+        &self,
+        account: &str,
+        token: &str,
+        by_longest_chain: bool,
+    ) -> Result<TokenBalance> {
+        self.read(|c| async move { c.get_token_balance(account, token, by_longest_chain).await })
+            .await
+    }
+
+    pub async fn get_token_info(&self, symbol: &str, by_longest_chain: bool) -> Result<TokenInfo> {
+        self.read(|c| async move { c.get_token_info(symbol, by_longest_chain).await })
+            .await
+    }
+
+    pub async fn get_contract(&self, id: &str, by_longest_chain: bool) -> Result<Contract> {
+        self.read(|c| async move { c.get_contract(id, by_longest_chain).await })
+            .await
+    }
+
+    pub async fn get_contract_storage(&self, par: &ContractStoragePost) -> Result<ContractStorage> {
+        self.read(|c| async move { c.get_contract_storage(par).await })
+            .await
+    }
+
+    pub async fn get_contract_storage_fields(
+        &self,
+        par: &ContractStorageFieldsPost,
+    ) -> Result<ContractStorageFields> {
+        self.read(|c| async move { c.get_contract_storage_fields(par).await })
+            .await
+    }
+
+    pub async fn get_batch_contract_storage(
+        &self,
+        par: &BatchContractStoragePost,
+    ) -> Result<BatchContractStorage> {
+        self.read(|c| async move { c.get_batch_contract_storage(par).await })
+            .await
+    }
+
+    pub async fn get_producer_vote_info(
+        &self,
+        account: &str,
+        by_longest_chain: bool,
+    ) -> Result<ProducerVoteInfo> {
+        self.read(|c| async move { c.get_producer_vote_info(account, by_longest_chain).await })
+            .await
+    }
+
+    pub async fn get_candidate_bonus(
+        &self,
+        name: &str,
+        by_longest_chain: bool,
+    ) -> Result<CandidateBonus> {
+        self.read(|c| async move { c.get_candidate_bonus(name, by_longest_chain).await })
+            .await
+    }
+
+    pub async fn get_voter_bonus(&self, name: &str, by_longest_chain: bool) -> Result<VoterBonus> {
+        self.read(|c| async move { c.get_voter_bonus(name, by_longest_chain).await })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{MockTransport, Reply};
+    use crate::transport::block_on;
+    use crate::{NodeErrorKind, RetryPolicy};
+    use alloc::boxed::Box;
+    use alloc::format;
+    use core::task::Poll;
+    use futures_util::future::select;
+
+    /// Node at `head_block`, or down if `None`. Its requests in flight count towards those
+    /// of `others`.
+    fn node_with(head_block: Option<i64>, others: &MockTransport) -> MockTransport {
+        let transport = MockTransport::sharing_in_flight(others);
+        match head_block {
+            Some(head_block) => {
+                transport.set_head_block(head_block);
+                transport.set_lib_block(head_block);
+            }
+            None => transport.set_response("", Reply::Fail("connection refused".to_string())),
+        }
+        transport
+    }
+
+    fn node(head_block: Option<i64>) -> MockTransport {
+        node_with(head_block, &MockTransport::new())
+    }
+
+    /// Nodes counting their requests in flight together. Like `FailoverClient::new`, they
+    /// don't retry on their own.
+    fn failover(heads: &[Option<i64>], config: FailoverConfig) -> FailoverClient<MockTransport> {
+        let shared = MockTransport::new();
+        let clients = heads
+            .iter()
+            .enumerate()
+            .map(|(i, &head_block)| {
+                IostClient::with_transport(
+                    &format!("http://node{}", i),
+                    node_with(head_block, &shared),
+                )
+                .with_retry_policy(RetryPolicy::none())
+            })
+            .collect();
+        FailoverClient::with_clients(clients, config)
+    }
+
+    fn transport(client: &FailoverClient<MockTransport>, index: usize) -> &MockTransport {
+        client.nodes[index].client.transport()
+    }
+
+    /// requests to `prefix` the node at `index` got
+    fn count(client: &FailoverClient<MockTransport>, index: usize, prefix: &str) -> usize {
+        transport(client, index).count(prefix)
+    }
+
+    fn overlap_requests(client: &FailoverClient<MockTransport>) {
+        for index in 0..client.nodes.len() {
+            transport(client, index).overlap_requests();
+        }
+    }
+
+    #[test]
+    fn should_skip_down_and_lagging_nodes() {
+        let client = failover(&[None, Some(100), Some(1000)], FailoverConfig::default());
+        block_on(client.get_gas_ratio()).unwrap();
+        assert_eq!(count(&client, 2, "getGasRatio"), 1);
+        assert_eq!(count(&client, 1, "getGasRatio"), 0);
+        let status = client.node_status();
+        assert!(!status[0].healthy);
+        assert_eq!(status[1].head_block, 100);
+        assert_eq!(client.ranked(), vec![2]);
+    }
+
+    #[test]
+    fn should_probe_nodes_at_once_with_timeout() {
+        let client = failover(&[Some(10), Some(12), Some(11)], FailoverConfig::default());
+        transport(&client, 2).set_response("getChainInfo", Reply::Hang);
+        overlap_requests(&client);
+        block_on(client.check_health());
+        assert_eq!(transport(&client, 0).max_in_flight(), 3);
+        let status = client.node_status();
+        assert!(status[0].healthy && status[1].healthy);
+        assert!(!status[2].healthy);
+        assert_eq!(
+            transport(&client, 2).sleeps(),
+            vec![client.config().probe_timeout]
+        );
+        assert!(transport(&client, 0).sleeps().is_empty());
+    }
+
+    #[test]
+    fn should_refresh_health_in_the_background() {
+        let client = Arc::new(failover(&[Some(10), Some(12)], FailoverConfig::default()));
+        let checked = |times| {
+            let client = client.clone();
+            futures_util::future::poll_fn(move |cx| {
+                if count(&client, 0, "getChainInfo") >= times {
+                    Poll::Ready(())
+                } else {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            })
+        };
+        let refresh = FailoverClient::refresh_health(Arc::downgrade(&client));
+        block_on(select(Box::pin(refresh), checked(3)));
+        assert_eq!(count(&client, 1, "getChainInfo"), 3);
+        // each check waits `check_interval` for the next
+        let sleeps = transport(&client, 0).sleeps();
+        assert!(sleeps.len() >= 2);
+        assert!(sleeps
+            .iter()
+            .all(|&sleep| sleep == client.config().check_interval));
+
+        let refresh = FailoverClient::refresh_health(Arc::downgrade(&client));
+        drop(client);
+        // stops once the client is gone
+        block_on(refresh);
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn should_spawn_health_checks() {
+        let client = Arc::new(failover(&[Some(10)], FailoverConfig::default()));
+        let checks = client.spawn_health_checks();
+        while count(&client, 0, "getChainInfo") < 2 {
+            tokio::time::delay_for(Duration::from_millis(1)).await;
+        }
+        drop(client);
+        checks.await.unwrap();
+    }
+
+    #[test]
+    fn should_fail_over_reads() {
+        let failing = node(Some(1000));
+        failing.set_response("getGasRatio", Reply::Fail("connection reset".to_string()));
+        let client = FailoverClient::with_clients(
+            vec![
+                IostClient::with_transport("http://node0", failing)
+                    .with_retry_policy(RetryPolicy::none()),
+                IostClient::with_transport("http://node1", node(Some(990)))
+                    .with_retry_policy(RetryPolicy::none()),
+            ],
+            FailoverConfig::default(),
+        );
+        block_on(client.check_health());
+        assert_eq!(client.ranked(), vec![0, 1]);
+        block_on(client.get_gas_ratio()).unwrap();
+        assert_eq!(count(&client, 0, "getGasRatio"), 1);
+        assert_eq!(count(&client, 1, "getGasRatio"), 1);
+        assert!(!client.node_status()[0].healthy);
+    }
+
+    #[test]
+    fn should_fan_out_send_tx() {
+        let config = FailoverConfig {
+            send_fan_out: 2,
+            ..FailoverConfig::default()
+        };
+        let client = failover(&[Some(10), None, Some(12), Some(11)], config);
+        block_on(client.check_health());
+        overlap_requests(&client);
+        let tx = Tx::new(0, 0, 1024, vec![]);
+        let response = block_on(client.send_tx(&tx)).unwrap();
+        // both sends were in flight at once
+        assert_eq!(transport(&client, 0).max_in_flight(), 2);
+        assert_eq!(response.hash, tx.hash().unwrap().to_string());
+        assert_eq!(count(&client, 2, "sendTx"), 1);
+        assert_eq!(count(&client, 3, "sendTx"), 1);
+        assert_eq!(count(&client, 0, "sendTx"), 0);
+    }
+
+    #[test]
+    fn should_only_fail_over_send_tx_on_node_failure() {
+        let client = failover(&[Some(12), Some(11), Some(10)], FailoverConfig::default());
+        transport(&client, 0).set_response("sendTx", Reply::Fail("connection reset".to_string()));
+        let tx = Tx::new(0, 0, 1024, vec![]);
+        assert!(block_on(client.send_tx(&tx)).is_ok());
+        assert!(!client.node_status()[0].healthy);
+        assert_eq!(count(&client, 1, "sendTx"), 1);
+
+        transport(&client, 1).set_response("sendTx", Reply::error(400, 3, "balance not enough"));
+        match block_on(client.send_tx(&tx)) {
+            Err(Error::NodeError(NodeErrorKind::BalanceNotEnough, _)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        // the rejection says nothing about the node, and another node would reject it too
+        assert!(client.node_status()[1].healthy);
+        assert_eq!(count(&client, 2, "sendTx"), 0);
+    }
+}
@@ -1,8 +1,11 @@
 use alloc::string::String;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::json::{JsonObject, NoStdDeserialize};
+
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct FrozenBalance {
@@ -11,3 +14,13 @@ pub struct FrozenBalance {
     /// the time when the amount is unfrozen
     pub time: String,
 }
+
+impl NoStdDeserialize for FrozenBalance {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(FrozenBalance {
+            amount: object.field("amount")?,
+            time: object.field("time")?,
+        })
+    }
+}
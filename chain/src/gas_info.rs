@@ -0,0 +1,39 @@
+use alloc::vec::Vec;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::pledge_info::PledgeInfo;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GasInfo {
+    /// Total gas for the moment
+    pub current_total: f64,
+    /// Gas available for trade
+    pub transferable_gas: f64,
+    /// Gas obtained from deposits
+    pub pledge_gas: f64,
+    /// The rate of gas increase, in gas per second
+    pub increase_speed: f64,
+    /// The upper limit of gas from token deposit
+    pub limit: f64,
+    /// The information on deposit made by other accounts, on behalf of the inquired account
+    pub pledged_info: Vec<PledgeInfo>,
+}
+
+impl NoStdDeserialize for GasInfo {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(GasInfo {
+            current_total: object.field("current_total")?,
+            transferable_gas: object.field("transferable_gas")?,
+            pledge_gas: object.field("pledge_gas")?,
+            increase_speed: object.field("increase_speed")?,
+            limit: object.field("limit")?,
+            pledged_info: object.field("pledged_info")?,
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::frozen_balance::FrozenBalance;
+use crate::gas_info::GasInfo;
+use crate::group::Group;
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::permission::Permission;
+use crate::ram_info::RAMInfo;
+use crate::vote_info::VoteInfo;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Account {
+    /// account name
+    pub name: String,
+    /// the balance of the account
+    pub balance: f64,
+    /// Gas information
+    pub gas_info: GasInfo,
+    /// Ram information
+    pub ram_info: RAMInfo,
+    /// permissions
+    pub permissions: BTreeMap<String, Permission>,
+    /// permission groups
+    pub groups: BTreeMap<String, Group>,
+    /// information on the frozen balance
+    pub frozen_balances: Vec<FrozenBalance>,
+    /// information of vote
+    pub vote_infos: Vec<VoteInfo>,
+}
+
+impl NoStdDeserialize for Account {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(Account {
+            name: object.field("name")?,
+            balance: object.field("balance")?,
+            gas_info: object.field("gas_info")?,
+            ram_info: object.field("ram_info")?,
+            permissions: object.field("permissions")?,
+            groups: object.field("groups")?,
+            frozen_balances: object.field("frozen_balances")?,
+            vote_infos: object.field("vote_infos")?,
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::key_field::KeyField;
+
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct BatchContractStoragePost {
+    /// smart contract ID
+    pub id: String,
+    /// the key-fields which are queried，the order of return values is the same as the request
+    pub key_fields: Vec<KeyField>,
+    /// true - get data from the longest chain; false - get data from irreversible blocks
+    pub by_longest_chain: bool,
+}
+
+impl BatchContractStoragePost {
+    pub fn no_std_serialize(&self) -> JsonValue {
+        JsonValue::Object(vec![
+            (
+                "id".chars().collect::<Vec<_>>(),
+                JsonValue::String(self.id.chars().collect()),
+            ),
+            (
+                "key_fields".chars().collect::<Vec<_>>(),
+                JsonValue::Array(
+                    self.key_fields
+                        .iter()
+                        .map(|e| e.no_std_serialize())
+                        .collect(),
+                ),
+            ),
+            (
+                "by_longest_chain".chars().collect::<Vec<_>>(),
+                JsonValue::Boolean(self.by_longest_chain),
+            ),
+        ])
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BatchContractStorage {
+    /// the stored data, returned in order as request
+    pub datas: Vec<String>,
+    /// the hash of block from which the data is from
+    pub block_hash: String,
+    /// the number of block from which the data is from
+    pub block_number: String,
+}
+
+impl NoStdDeserialize for BatchContractStorage {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(BatchContractStorage {
+            datas: object.field("datas")?,
+            block_hash: object.field("block_hash")?,
+            block_number: object.field("block_number")?,
+        })
+    }
+}
@@ -0,0 +1,45 @@
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::block::Block;
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::status::Status;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BlockByHash {
+    /// PENDING - block is in cache; IRREVERSIBLE - block is irreversible.
+    pub status: Status,
+    /// a Block struct
+    pub block: Block,
+}
+
+impl NoStdDeserialize for BlockByHash {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(BlockByHash {
+            status: object.field("status")?,
+            block: object.field("block")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BlockByNumber {
+    /// PENDING - block is in cache; IRREVERSIBLE - block is irreversible.
+    pub status: Status,
+    /// a Block struct
+    pub block: Block,
+}
+
+impl NoStdDeserialize for BlockByNumber {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(BlockByNumber {
+            status: object.field("status")?,
+            block: object.field("block")?,
+        })
+    }
+}
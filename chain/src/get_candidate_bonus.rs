@@ -0,0 +1,21 @@
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CandidateBonus {
+    /// the bonus he can receive
+    pub bonus: f64,
+}
+
+impl NoStdDeserialize for CandidateBonus {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(CandidateBonus {
+            bonus: object.field("bonus")?,
+        })
+    }
+}
@@ -1,60 +1,57 @@
-// use alloc::string::String;
-// use alloc::vec::Vec;
-// #[cfg(feature = "std")]
-// use serde::Deserialize;
-//
-// #[derive(Deserialize, Debug, Default)]
-// pub struct ChainInfo {
-//     /// Network name, such as "mainnet" or "testnet"
-//     pub net_name: String,
-//     /// iost protocol version
-//     pub protocol_version: String,
-//     /// iost chain id
-//     pub chain_id: i32,
-//     /// the lastest block height
-//     pub head_block: String,
-//     /// the hash of the lastest block
-//     pub head_block_hash: String,
-//     /// height of irreversible blocks
-//     pub lib_block: String,
-//     /// hash of irreversible blocks
-//     pub lib_block_hash: String,
-//     /// list of pubkeys for the current block production nodes
-//     pub witness_list: Vec<String>,
-//     /// list of pubkeys for the block production nodes of the last irreversible block time
-//     pub lib_witness_list: Vec<String>,
-//     /// list of pubkeys for the next round block production nodes
-//     pub pending_witness_list: Vec<String>,
-//     /// time of head block
-//     pub head_block_time: String,
-//     /// time of last irreversible block
-//     pub lib_block_time: String,
-// }
-//
-// fn get_chain_info() -> ChainInfo {
-//     // let res = reqwest::get("https://api.iost.io/getChainInfo").await.unwrap()
-//     //     .json::<ChainInfo>()
-//     //     .await.unwrap();
-//
-//     let mut chain_info = ChainInfo::default();
-//     // let client = reqwest::Client::new();
-//     // let res = client.get("https://api.iost.io/getChainInfo")
-//     //     // .header(UserAgent::new("foo"))
-//     //     .send()?;
-//     let body: ChainInfo = reqwest::blocking::get("http://127.0.0.1:30001/getChainInfo")
-//         .unwrap()
-//         .json()
-//         .unwrap();
-//     dbg!(body);
-//     chain_info
-// }
-//
-// #[cfg(test)]
-// mod test {
-//     use super::*;
-//
-//     #[test]
-//     fn get_chain_info_should_be_ok() {
-//         println!("{:#?}", get_chain_info());
-//     }
-// }
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ChainInfo {
+    /// Network name, such as "mainnet" or "testnet"
+    pub net_name: String,
+    /// iost protocol version
+    pub protocol_version: String,
+    /// iost chain id
+    pub chain_id: i32,
+    /// the lastest block height
+    pub head_block: String,
+    /// the hash of the lastest block
+    pub head_block_hash: String,
+    /// height of irreversible blocks
+    pub lib_block: String,
+    /// hash of irreversible blocks
+    pub lib_block_hash: String,
+    /// list of pubkeys for the current block production nodes
+    pub witness_list: Vec<String>,
+    /// list of pubkeys for the block production nodes of the last irreversible block time
+    pub lib_witness_list: Vec<String>,
+    /// list of pubkeys for the next round block production nodes
+    pub pending_witness_list: Vec<String>,
+    /// time of head block
+    pub head_block_time: String,
+    /// time of last irreversible block
+    pub lib_block_time: String,
+}
+
+impl NoStdDeserialize for ChainInfo {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(ChainInfo {
+            net_name: object.field("net_name")?,
+            protocol_version: object.field("protocol_version")?,
+            chain_id: object.field("chain_id")?,
+            head_block: object.field("head_block")?,
+            head_block_hash: object.field("head_block_hash")?,
+            lib_block: object.field("lib_block")?,
+            lib_block_hash: object.field("lib_block_hash")?,
+            witness_list: object.field("witness_list")?,
+            lib_witness_list: object.field("lib_witness_list")?,
+            pending_witness_list: object.field("pending_witness_list")?,
+            head_block_time: object.field("head_block_time")?,
+            lib_block_time: object.field("lib_block_time")?,
+        })
+    }
+}
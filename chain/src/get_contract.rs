@@ -0,0 +1,37 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::abi::ABI;
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Contract {
+    /// contract ID
+    pub id: String,
+    /// the code of the contract
+    pub code: String,
+    /// the language of the contract
+    pub language: String,
+    /// contract version
+    pub version: String,
+    /// the ABIs of the contract
+    pub abis: Vec<ABI>,
+}
+
+impl NoStdDeserialize for Contract {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(Contract {
+            id: object.field("id")?,
+            code: object.field("code")?,
+            language: object.field("language")?,
+            version: object.field("version")?,
+            abis: object.field("abis")?,
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct ContractStoragePost {
+    /// ID of the smart contract
+    pub id: String,
+    /// the key of StateDB
+    pub key: String,
+    /// the values from StateDB; if StateDB[key] is a map then it is required to configure field to obtain values of StateDB[key][field]
+    pub field: String,
+    /// true - get data from the longest chain; false - get data from irreversible blocks
+    pub by_longest_chain: bool,
+}
+
+impl ContractStoragePost {
+    pub fn no_std_serialize(&self) -> JsonValue {
+        JsonValue::Object(vec![
+            (
+                "id".chars().collect::<Vec<_>>(),
+                JsonValue::String(self.id.chars().collect()),
+            ),
+            (
+                "key".chars().collect::<Vec<_>>(),
+                JsonValue::String(self.key.chars().collect()),
+            ),
+            (
+                "field".chars().collect::<Vec<_>>(),
+                JsonValue::String(self.field.chars().collect()),
+            ),
+            (
+                "by_longest_chain".chars().collect::<Vec<_>>(),
+                JsonValue::Boolean(self.by_longest_chain),
+            ),
+        ])
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ContractStorage {
+    /// the stored data
+    pub data: String,
+    /// the hash of block from which the data is from
+    pub block_hash: String,
+    /// the number of block from which the data is from
+    pub block_number: String,
+}
+
+impl NoStdDeserialize for ContractStorage {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(ContractStorage {
+            data: object.field("data")?,
+            block_hash: object.field("block_hash")?,
+            block_number: object.field("block_number")?,
+        })
+    }
+}
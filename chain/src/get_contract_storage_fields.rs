@@ -0,0 +1,55 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct ContractStorageFieldsPost {
+    /// ID of the smart contract
+    pub id: String,
+    /// the key of StateDB
+    pub key: String,
+    /// true - get data from the longest chain; false - get data from irreversible blocks
+    pub by_longest_chain: bool,
+}
+
+impl ContractStorageFieldsPost {
+    pub fn no_std_serialize(&self) -> JsonValue {
+        JsonValue::Object(vec![
+            (
+                "id".chars().collect::<Vec<_>>(),
+                JsonValue::String(self.id.chars().collect()),
+            ),
+            (
+                "key".chars().collect::<Vec<_>>(),
+                JsonValue::String(self.key.chars().collect()),
+            ),
+            (
+                "by_longest_chain".chars().collect::<Vec<_>>(),
+                JsonValue::Boolean(self.by_longest_chain),
+            ),
+        ])
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ContractStorageFields {
+    /// the fields of StateDB[key]
+    pub fields: Vec<String>,
+}
+
+impl NoStdDeserialize for ContractStorageFields {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(ContractStorageFields {
+            fields: object.field("fields")?,
+        })
+    }
+}
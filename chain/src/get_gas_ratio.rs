@@ -0,0 +1,24 @@
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GasRatio {
+    /// the lowest gas ratio of the most recently packed blocks
+    pub lowest_gas_ratio: f64,
+    /// the median gas ratio of the most recently packed blocks
+    pub median_gas_ratio: f64,
+}
+
+impl NoStdDeserialize for GasRatio {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(GasRatio {
+            lowest_gas_ratio: object.field("lowest_gas_ratio")?,
+            median_gas_ratio: object.field("median_gas_ratio")?,
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use alloc::string::String;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::net_work_info::NetWork;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct NodeInfo {
+    /// Building time of the 'server' binary
+    pub build_time: String,
+    /// Git hash of the 'iserver' binary
+    pub git_hash: String,
+    /// Current mode of the server. It can be one of 'ModeInit', 'ModeNormal' and 'ModeSync'
+    pub mode: String,
+    /// Network information of the node
+    pub network: NetWork,
+    /// the version of code
+    pub code_version: String,
+    /// the current timestamp of the server, unit is nano second
+    pub server_time: String,
+}
+
+impl NoStdDeserialize for NodeInfo {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(NodeInfo {
+            build_time: object.field("build_time")?,
+            git_hash: object.field("git_hash")?,
+            mode: object.field("mode")?,
+            network: object.field("network")?,
+            code_version: object.field("code_version")?,
+            server_time: object.field("server_time")?,
+        })
+    }
+}
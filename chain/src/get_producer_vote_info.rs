@@ -0,0 +1,50 @@
+use alloc::string::String;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use crate::de::de_number_or_string_to_f64;
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProducerVoteInfo {
+    /// the public key of the producer
+    pub pubkey: String,
+    /// location of the producer
+    pub loc: String,
+    /// website of the producer
+    pub url: String,
+    /// network ID of the producer node
+    pub net_id: String,
+    /// whether the account is a block producer
+    pub is_producer: bool,
+    /// registration status, such as "APPLY", "APPROVED" or "UNAPPLY"
+    pub status: String,
+    /// whether the producer is online
+    pub online: bool,
+    /// number of votes received
+    #[cfg_attr(
+        feature = "std",
+        serde(deserialize_with = "de_number_or_string_to_f64")
+    )]
+    pub votes: f64,
+}
+
+impl NoStdDeserialize for ProducerVoteInfo {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(ProducerVoteInfo {
+            pubkey: object.field("pubkey")?,
+            loc: object.field("loc")?,
+            url: object.field("url")?,
+            net_id: object.field("net_id")?,
+            is_producer: object.field("is_producer")?,
+            status: object.field("status")?,
+            online: object.field("online")?,
+            votes: object.field("votes")?,
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use alloc::string::String;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RamInfo {
+    /// RAM available, in byte
+    pub available_ram: String,
+    /// The amount of RAM sold, in byte
+    pub used_ram: String,
+    /// The system's total RAM count, in byte
+    pub total_ram: String,
+    /// The buying price of RAM, in IOST/byte
+    pub buy_price: f64,
+    /// The selling price of RAM, in IOST/byte
+    pub sell_price: f64,
+}
+
+impl NoStdDeserialize for RamInfo {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(RamInfo {
+            available_ram: object.field("available_ram")?,
+            used_ram: object.field("used_ram")?,
+            total_ram: object.field("total_ram")?,
+            buy_price: object.field("buy_price")?,
+            sell_price: object.field("sell_price")?,
+        })
+    }
+}
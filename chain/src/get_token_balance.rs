@@ -0,0 +1,27 @@
+use alloc::vec::Vec;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::frozen_balance::FrozenBalance;
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TokenBalance {
+    /// balance
+    pub balance: f64,
+    /// frozen balances
+    pub frozen_balances: Vec<FrozenBalance>,
+}
+
+impl NoStdDeserialize for TokenBalance {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(TokenBalance {
+            balance: object.field("balance")?,
+            frozen_balances: object.field("frozen_balances")?,
+        })
+    }
+}
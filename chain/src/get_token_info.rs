@@ -0,0 +1,50 @@
+use alloc::string::String;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TokenInfo {
+    /// token symbol
+    pub symbol: String,
+    /// token full name
+    pub full_name: String,
+    /// token issuer
+    pub issuer: String,
+    /// total amount of token supply, is the result of total_supply_float multiplied by decimal
+    pub total_supply: String,
+    /// current amount of token supply, is the result of current_supply_float multiplied by decimal
+    pub current_supply: String,
+    /// total amount of token supply
+    pub total_supply_float: f64,
+    /// current amount of token supply
+    pub current_supply_float: f64,
+    /// token decimal
+    pub decimal: i32,
+    /// whether the token can be transfered
+    pub can_transfer: bool,
+    /// whether the token can only be transfered by issuer
+    pub only_issuer_can_transfer: bool,
+}
+
+impl NoStdDeserialize for TokenInfo {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(TokenInfo {
+            symbol: object.field("symbol")?,
+            full_name: object.field("full_name")?,
+            issuer: object.field("issuer")?,
+            total_supply: object.field("total_supply")?,
+            current_supply: object.field("current_supply")?,
+            total_supply_float: object.field("total_supply_float")?,
+            current_supply_float: object.field("current_supply_float")?,
+            decimal: object.field("decimal")?,
+            can_transfer: object.field("can_transfer")?,
+            only_issuer_can_transfer: object.field("only_issuer_can_transfer")?,
+        })
+    }
+}
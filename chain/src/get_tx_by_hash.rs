@@ -0,0 +1,91 @@
+use alloc::string::String;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::status::Status;
+use crate::transaction::Transaction;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GetTxByHash {
+    /// enum PENDING- transaction is cached, PACKED - transaction is in reversible blocks, IRREVERSIBLE - transaction is in irreversible blocks
+    pub status: Status,
+    /// Transaction data
+    pub transaction: Transaction,
+    /// the number of the block which the tx is in
+    pub block_number: String,
+}
+
+impl NoStdDeserialize for GetTxByHash {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(GetTxByHash {
+            status: object.field("status")?,
+            transaction: object.field("transaction")?,
+            block_number: object.field("block_number")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TX_BY_HASH: &str = r#"
+    {
+        "status": "IRREVERSIBLE",
+        "transaction": {
+            "hash": "Dj8bmA4Fx4LHrwLtDB6EEkNbBFU8biENxf55mNaJewYw",
+            "time": "1545901447135305000",
+            "expiration": "1545901537135305000",
+            "gas_ratio": 1,
+            "gas_limit": 1000000,
+            "delay": "0",
+            "chain_id": 1024,
+            "actions": [{
+                "contract": "token.iost",
+                "action_name": "transfer",
+                "data": "[\"iost\",\"admin\",\"lispczz3\",\"100\",\"\"]"
+            }],
+            "signers": [],
+            "publisher": "admin",
+            "referred_tx": "",
+            "amount_limit": [{"token": "*", "value": "unlimited"}],
+            "tx_receipt": {
+                "tx_hash": "Dj8bmA4Fx4LHrwLtDB6EEkNbBFU8biENxf55mNaJewYw",
+                "gas_usage": 2577,
+                "ram_usage": {},
+                "status_code": "SUCCESS",
+                "message": "",
+                "returns": ["[]"],
+                "receipts": []
+            }
+        },
+        "block_number": "1264"
+    }
+    "#;
+
+    #[test]
+    fn should_deserialize_tx_by_hash() {
+        let tx: GetTxByHash = serde_json::from_str(TX_BY_HASH).unwrap();
+        assert_eq!(tx.status, Status::IRREVERSIBLE);
+        assert_eq!(tx.transaction.time, 1545901447135305000);
+        assert_eq!(tx.transaction.gas_limit, 1000000.0);
+        assert_eq!(tx.transaction.tx_receipt.unwrap().gas_usage, 2577.0);
+        assert_eq!(tx.block_number, "1264");
+    }
+
+    #[test]
+    fn should_no_std_deserialize_tx_by_hash() {
+        let tx: GetTxByHash = crate::json::from_slice(TX_BY_HASH.as_bytes()).unwrap();
+        assert_eq!(tx.status, Status::IRREVERSIBLE);
+        assert_eq!(tx.transaction.time, 1545901447135305000);
+        assert_eq!(tx.transaction.actions[0].contract, b"token.iost".to_vec());
+        assert_eq!(tx.transaction.amount_limit[0].value, "unlimited");
+        assert_eq!(tx.transaction.tx_receipt.unwrap().gas_usage, 2577.0);
+        assert_eq!(tx.block_number, "1264");
+    }
+}
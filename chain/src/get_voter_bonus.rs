@@ -0,0 +1,27 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VoterBonus {
+    /// the total voting bonus he can receive
+    pub bonus: f64,
+    /// the bonus from every candidate
+    pub detail: BTreeMap<String, f64>,
+}
+
+impl NoStdDeserialize for VoterBonus {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(VoterBonus {
+            bonus: object.field("bonus")?,
+            detail: object.field("detail")?,
+        })
+    }
+}
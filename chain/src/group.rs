@@ -2,6 +2,8 @@ use alloc::string::String;
 use alloc::vec::Vec;
 
 use crate::item::Item;
+use crate::json::{JsonObject, NoStdDeserialize};
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
@@ -13,3 +15,13 @@ pub struct Group {
     /// information on the permission group
     pub items: Vec<Item>,
 }
+
+impl NoStdDeserialize for Group {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(Group {
+            name: object.field("name")?,
+            items: object.field("items")?,
+        })
+    }
+}
@@ -1,9 +1,12 @@
 use alloc::vec::Vec;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+use crate::json::{JsonObject, NoStdDeserialize};
+
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Info {
     /// mode of concurrency; 0 - non-concurrent; 1 - concurrent
@@ -13,3 +16,14 @@ pub struct Info {
     /// indices of the transaction
     pub batch_index: Vec<i32>,
 }
+
+impl NoStdDeserialize for Info {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(Info {
+            mode: object.field("mode")?,
+            thread: object.field("thread")?,
+            batch_index: object.field("batch_index")?,
+        })
+    }
+}
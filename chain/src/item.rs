@@ -1,8 +1,11 @@
 use alloc::string::String;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::json::{JsonObject, NoStdDeserialize};
+
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Item {
@@ -15,3 +18,15 @@ pub struct Item {
     /// the permission
     pub permission: String,
 }
+
+impl NoStdDeserialize for Item {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(Item {
+            id: object.field("id")?,
+            is_key_pair: object.field("is_key_pair")?,
+            weight: object.field("weight")?,
+            permission: object.field("permission")?,
+        })
+    }
+}
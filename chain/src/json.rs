@@ -0,0 +1,168 @@
+//! Decoding of node API responses with lite-json, for builds without `std`.
+//!
+//! Mirrors the serde impls: missing fields are an error unless the serde side
+//! marks them `default`, and 64-bit numbers may arrive as JSON strings.
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use lite_json::{parse_json, JsonValue, NumberValue};
+
+use crate::{Error, Result};
+
+pub trait NoStdDeserialize: Sized {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self>;
+}
+
+/// Parse a response body and decode it with [`NoStdDeserialize`].
+pub fn from_slice<T: NoStdDeserialize>(body: &[u8]) -> Result<T> {
+    let body = core::str::from_utf8(body).map_err(|_| Error::JsonParserError())?;
+    let value = parse_json(body).map_err(|_| Error::JsonParserError())?;
+    T::no_std_deserialize(&value)
+}
+
+/// Field access on a JSON object.
+pub struct JsonObject<'a>(&'a [(Vec<char>, JsonValue)]);
+
+impl<'a> JsonObject<'a> {
+    pub fn new(value: &'a JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Object(fields) => Ok(JsonObject(fields)),
+            _ => Err(Error::JsonParserError()),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'a JsonValue> {
+        self.0
+            .iter()
+            .find(|(k, _)| k.iter().copied().eq(key.chars()))
+            .map(|(_, v)| v)
+    }
+
+    /// required field
+    pub fn field<T: NoStdDeserialize>(&self, key: &str) -> Result<T> {
+        let value = self.get(key).ok_or(Error::JsonParserError())?;
+        T::no_std_deserialize(value)
+    }
+
+    /// field that falls back to `T::default()` when missing or null
+    pub fn field_or_default<T: NoStdDeserialize + Default>(&self, key: &str) -> Result<T> {
+        match self.get(key) {
+            None | Some(JsonValue::Null) => Ok(T::default()),
+            Some(value) => T::no_std_deserialize(value),
+        }
+    }
+}
+
+fn number_to_f64(number: &NumberValue) -> f64 {
+    number.clone().to_f64()
+}
+
+impl NoStdDeserialize for String {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::String(s) => Ok(s.iter().collect()),
+            _ => Err(Error::JsonParserError()),
+        }
+    }
+}
+
+impl NoStdDeserialize for bool {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Boolean(b) => Ok(*b),
+            _ => Err(Error::JsonParserError()),
+        }
+    }
+}
+
+impl NoStdDeserialize for i64 {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Number(n) if n.fraction_length == 0 && n.exponent == 0 => Ok(n.integer),
+            JsonValue::Number(n) => Ok(number_to_f64(n) as i64),
+            JsonValue::String(s) => s
+                .iter()
+                .collect::<String>()
+                .parse::<i64>()
+                .map_err(|_| Error::JsonParserError()),
+            _ => Err(Error::JsonParserError()),
+        }
+    }
+}
+
+impl NoStdDeserialize for i32 {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        let n = i64::no_std_deserialize(value)?;
+        if n < i32::MIN as i64 || n > i32::MAX as i64 {
+            return Err(Error::JsonParserError());
+        }
+        Ok(n as i32)
+    }
+}
+
+impl NoStdDeserialize for f64 {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Number(n) => Ok(number_to_f64(n)),
+            JsonValue::String(s) => s
+                .iter()
+                .collect::<String>()
+                .parse::<f64>()
+                .map_err(|_| Error::JsonParserError()),
+            _ => Err(Error::JsonParserError()),
+        }
+    }
+}
+
+impl<T: NoStdDeserialize> NoStdDeserialize for Option<T> {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Null => Ok(None),
+            value => T::no_std_deserialize(value).map(Some),
+        }
+    }
+}
+
+impl<T: NoStdDeserialize> NoStdDeserialize for Vec<T> {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Array(values) => values.iter().map(T::no_std_deserialize).collect(),
+            _ => Err(Error::JsonParserError()),
+        }
+    }
+}
+
+impl<T: NoStdDeserialize> NoStdDeserialize for BTreeMap<String, T> {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Object(fields) => fields
+                .iter()
+                .map(|(k, v)| Ok((k.iter().collect(), T::no_std_deserialize(v)?)))
+                .collect(),
+            _ => Err(Error::JsonParserError()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_decode_numbers_and_strings() {
+        let value = parse_json(r#"{"a": "12", "b": 3, "c": 1.5, "d": "2.5", "e": null}"#).unwrap();
+        let object = JsonObject::new(&value).unwrap();
+        assert_eq!(object.field::<i64>("a").unwrap(), 12);
+        assert_eq!(object.field::<i32>("b").unwrap(), 3);
+        assert_eq!(object.field::<f64>("c").unwrap(), 1.5);
+        assert_eq!(object.field::<f64>("d").unwrap(), 2.5);
+        assert_eq!(object.field::<Option<String>>("e").unwrap(), None);
+        assert_eq!(
+            object.field_or_default::<Vec<String>>("f").unwrap().len(),
+            0
+        );
+        assert!(object.field::<String>("f").is_err());
+    }
+}
@@ -1,5 +1,8 @@
 use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::Serialize;
 
@@ -10,3 +13,18 @@ pub struct KeyField {
     /// the values from StateDB; if StateDB[key] is a map then it is required to configure field to obtain values of StateDB[key][field]
     pub field: String,
 }
+
+impl KeyField {
+    pub fn no_std_serialize(&self) -> JsonValue {
+        JsonValue::Object(vec![
+            (
+                "key".chars().collect::<Vec<_>>(),
+                JsonValue::String(self.key.chars().collect()),
+            ),
+            (
+                "field".chars().collect::<Vec<_>>(),
+                JsonValue::String(self.field.chars().collect()),
+            ),
+        ])
+    }
+}
@@ -5,49 +5,130 @@ extern crate alloc;
 pub mod abi;
 pub mod action;
 pub mod amount_limit;
+pub mod block;
+pub mod block_follower;
+pub mod bulk;
 pub mod bytes;
+#[cfg(feature = "std")]
+pub mod cache;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
 
 mod chain_test;
 
 pub mod spv;
 pub mod verify;
 
+pub mod client;
+#[cfg(feature = "client")]
+pub mod config;
+#[cfg(all(test, feature = "std"))]
+mod conformance;
+
+mod de;
+mod deferred;
+
 pub mod error;
+pub mod estimate;
+#[cfg(feature = "std")]
+pub mod failover;
 pub mod frozen_balance;
+pub mod gas_info;
+pub mod get_account;
+pub mod get_batch_contract_storage;
+pub mod get_block_by_hash;
+pub mod get_candidate_bonus;
+pub mod get_chain_info;
+pub mod get_contract;
+pub mod get_contract_storage;
+pub mod get_contract_storage_fields;
+pub mod get_gas_ratio;
+pub mod get_node_info;
+pub mod get_producer_vote_info;
+pub mod get_ram_info;
+pub mod get_token_balance;
+pub mod get_token_info;
+pub mod get_tx_by_hash;
+pub mod get_voter_bonus;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod group;
 pub mod info;
 pub mod item;
+pub mod json;
 pub mod key_field;
 pub mod message;
+#[cfg(all(test, feature = "std"))]
+mod mock;
 pub mod names;
 pub mod net_work_info;
+pub mod node_error;
+pub mod partial_tx;
 pub mod permission;
 pub mod pledge_info;
 pub mod ram_info;
 pub mod receipts;
+pub mod retry;
 pub mod signature;
+pub mod signer;
 pub mod status;
 pub mod status_code;
+pub mod subscribe;
+mod telemetry;
 pub mod test;
 pub mod time_point;
 pub mod transaction;
+pub mod transport;
 pub mod tx;
+pub mod tx_builder;
+mod tx_decode;
+pub mod tx_hash;
 pub mod tx_receipt;
 pub mod tx_response;
+pub mod tx_tracker;
 pub mod unsigned_int;
+pub mod validate;
 pub mod vote_info;
 
-mod get_chain_info;
-
 pub use iost_derive::*;
 
 pub use self::{
-    abi::*, action::*, amount_limit::*, bytes::*, error::*, frozen_balance::*, group::*, info::*,
-    item::*, key_field::*, message::*, names::*, net_work_info::*, permission::*, pledge_info::*,
-    ram_info::*, receipts::*, signature::*, status::*, status_code::*, transaction::*, tx::*,
-    tx_receipt::*, tx_response::*, unsigned_int::*, vote_info::*,
+    abi::*, action::*, amount_limit::*, block::*, bytes::*, error::*, frozen_balance::*,
+    gas_info::*, get_account::*, get_batch_contract_storage::*, get_block_by_hash::*,
+    get_candidate_bonus::*, get_chain_info::*, get_contract::*, get_contract_storage::*,
+    get_contract_storage_fields::*, get_gas_ratio::*, get_node_info::*, get_producer_vote_info::*,
+    get_ram_info::*, get_token_balance::*, get_token_info::*, get_tx_by_hash::*,
+    get_voter_bonus::*, group::*, info::*, item::*, key_field::*, message::*, names::*,
+    net_work_info::*, permission::*, pledge_info::*, ram_info::*, receipts::*, signature::*,
+    status::*, status_code::*, transaction::*, tx::*, tx_receipt::*, tx_response::*,
+    unsigned_int::*, vote_info::*,
 };
 
+pub use self::block_follower::{BlockEvent, BlockFollower, FollowerConfig, FollowerCursor};
+#[cfg(feature = "std")]
+pub use self::cache::{CacheConfig, CacheStats, CachedClient};
+pub use self::client::IostClient;
+pub use self::estimate::Estimate;
+pub use self::node_error::NodeErrorKind;
+pub use self::partial_tx::{PartialTx, PartialTxStatus, SignerSignature};
+pub use self::retry::RetryPolicy;
+pub use self::signer::{KeySigner, TxSigner};
+pub use self::subscribe::{Event, EventFilter, EventTopic, SubscribeRequest};
+#[cfg(feature = "std")]
+pub use self::failover::{FailoverClient, FailoverConfig, NodeStatus};
+pub use self::transport::{HttpResponse, HttpStreamResponse, HttpTransport};
+pub use self::tx_builder::TxBuilder;
+pub use self::tx_hash::TxHash;
+pub use self::tx_tracker::{Confirmation, ConfirmationLevel, TrackerConfig, TxTracker};
+pub use self::validate::{ChainParams, Violation};
+#[cfg(feature = "grpc")]
+pub use self::grpc::GrpcClient;
+#[cfg(feature = "client")]
+pub use self::config::ClientConfig;
+#[cfg(feature = "client")]
+pub use self::transport::ReqwestTransport;
+
 use alloc::vec;
 use alloc::vec::Vec;
 
@@ -1,8 +1,11 @@
 use alloc::string::String;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::json::{JsonObject, NoStdDeserialize};
+
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ErrorMessage {
@@ -11,3 +14,13 @@ pub struct ErrorMessage {
     /// error message
     pub message: String,
 }
+
+impl NoStdDeserialize for ErrorMessage {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(ErrorMessage {
+            code: object.field("code")?,
+            message: object.field("message")?,
+        })
+    }
+}
@@ -0,0 +1,343 @@
+//! An in-memory node for the client tests. Answers every endpoint with the fixtures of
+//! `iost-mock-node`, which this crate can't depend on, so the tests of every module share one
+//! fake node instead of each hand-rolling a partial one. Like `MockNode`, answers can be
+//! replaced per endpoint.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use alloc::boxed::Box;
+use async_trait::async_trait;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+use futures_util::stream;
+use serde_json::{json, Value};
+
+use crate::transport::{HttpResponse, HttpStreamResponse, HttpTransport};
+use crate::{Error, Result, Tx};
+
+#[path = "../../mock-node/src/fixtures.rs"]
+pub(crate) mod fixtures;
+
+/// A request received by the mock transport.
+#[derive(Debug, Clone)]
+pub(crate) struct Request {
+    pub method: &'static str,
+    pub url: String,
+    /// path without the host, e.g. `getBlockByNumber/3/true`
+    pub path: String,
+    pub body: Option<Vec<u8>>,
+}
+
+impl Request {
+    /// the `/` separated parts of the path
+    pub fn segments(&self) -> Vec<&str> {
+        self.path.split('/').collect()
+    }
+}
+
+/// How the mock transport answers a request.
+#[derive(Debug, Clone)]
+pub(crate) enum Reply {
+    /// status code and body
+    Status(u16, Vec<u8>),
+    /// a transport error, like a refused connection
+    Fail(String),
+    /// no answer, ever
+    Hang,
+    /// the answer of the fixtures, as if no handler were set
+    Fixture,
+}
+
+impl Reply {
+    pub fn json(status: u16, body: Value) -> Self {
+        Reply::Status(status, body.to_string().into_bytes())
+    }
+
+    pub fn ok(body: Value) -> Self {
+        Self::json(200, body)
+    }
+
+    /// an error body in the format of the node's gateway
+    pub fn error(status: u16, code: i32, message: &str) -> Self {
+        Self::json(status, fixtures::error(code, message))
+    }
+
+    pub fn text(status: u16, body: &str) -> Self {
+        Reply::Status(status, body.as_bytes().to_vec())
+    }
+}
+
+type Handler = Arc<dyn Fn(&Request) -> Reply + Send + Sync>;
+
+struct State {
+    head_block: i64,
+    lib_block: i64,
+    handlers: Vec<(String, Handler)>,
+    streams: Vec<(u16, Vec<&'static str>)>,
+    requests: Vec<Request>,
+    sleeps: Vec<Duration>,
+}
+
+/// requests in flight, and the most seen at once
+type InFlight = Arc<Mutex<(usize, usize)>>;
+
+/// An `HttpTransport` answering like `MockNode`, without a server. Waits passed to `sleep`
+/// are recorded and end after one poll, so a request racing a wait wins unless it hangs.
+pub(crate) struct MockTransport {
+    state: Mutex<State>,
+    in_flight: InFlight,
+    overlap: AtomicBool,
+}
+
+impl Default for MockTransport {
+    fn default() -> Self {
+        MockTransport {
+            state: Mutex::new(State {
+                head_block: 100,
+                lib_block: 90,
+                handlers: Vec::new(),
+                streams: Vec::new(),
+                requests: Vec::new(),
+                sleeps: Vec::new(),
+            }),
+            in_flight: InFlight::default(),
+            overlap: AtomicBool::new(false),
+        }
+    }
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A transport counting its requests in flight together with those of `other`, for
+    /// requests spread over several nodes.
+    pub fn sharing_in_flight(other: &MockTransport) -> Self {
+        MockTransport {
+            in_flight: other.in_flight.clone(),
+            ..Self::default()
+        }
+    }
+
+    pub fn set_head_block(&self, number: i64) {
+        self.state.lock().unwrap().head_block = number;
+    }
+
+    pub fn set_lib_block(&self, number: i64) {
+        self.state.lock().unwrap().lib_block = number;
+    }
+
+    /// Answer requests whose path starts with `prefix` with `handler`, instead of the
+    /// fixtures. Handlers registered later take precedence.
+    pub fn set_handler<F>(&self, prefix: &str, handler: F)
+    where
+        F: Fn(&Request) -> Reply + Send + Sync + 'static,
+    {
+        self.state
+            .lock()
+            .unwrap()
+            .handlers
+            .push((prefix.to_string(), Arc::new(handler)));
+    }
+
+    /// Answer requests whose path starts with `prefix` with a fixed reply.
+    pub fn set_response(&self, prefix: &str, reply: Reply) {
+        self.set_handler(prefix, move |_| reply.clone());
+    }
+
+    /// Answer requests whose path starts with `prefix` with `replies` in order, repeating
+    /// the last one.
+    pub fn set_responses(&self, prefix: &str, replies: Vec<Reply>) {
+        let replies = Mutex::new(replies);
+        self.set_handler(prefix, move |_| {
+            let mut replies = replies.lock().unwrap();
+            if replies.len() > 1 {
+                replies.remove(0)
+            } else {
+                replies[0].clone()
+            }
+        });
+    }
+
+    /// Serve one streamed response per `post_stream` connection, in order, each a status
+    /// code and the chunks of its body. Connections past the last are refused.
+    pub fn set_streams(&self, streams: Vec<(u16, Vec<&'static str>)>) {
+        self.state.lock().unwrap().streams = streams;
+    }
+
+    /// Keep every request pending for one poll, so concurrent requests overlap and
+    /// [`MockTransport::max_in_flight`] counts them.
+    pub fn overlap_requests(&self) {
+        self.overlap.store(true, Ordering::SeqCst);
+    }
+
+    /// every request received so far
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// number of requests received so far whose path starts with `prefix`
+    pub fn count(&self, prefix: &str) -> usize {
+        let state = self.state.lock().unwrap();
+        state
+            .requests
+            .iter()
+            .filter(|request| request.path.starts_with(prefix))
+            .count()
+    }
+
+    /// every wait passed to `sleep` so far
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.state.lock().unwrap().sleeps.clone()
+    }
+
+    /// the most requests in flight at once
+    pub fn max_in_flight(&self) -> usize {
+        self.in_flight.lock().unwrap().1
+    }
+
+    async fn request(
+        &self,
+        method: &'static str,
+        url: &str,
+        body: Option<Vec<u8>>,
+    ) -> Result<HttpResponse> {
+        let request = Request {
+            method,
+            url: url.to_string(),
+            path: path(url),
+            body,
+        };
+        let handler = {
+            let mut state = self.state.lock().unwrap();
+            state.requests.push(request.clone());
+            state
+                .handlers
+                .iter()
+                .rev()
+                .find(|(prefix, _)| request.path.starts_with(prefix.as_str()))
+                .map(|(_, handler)| handler.clone())
+        };
+        // handlers run unlocked, so they can inspect the transport
+        let reply = handler.map_or(Reply::Fixture, |handler| handler(&request));
+
+        {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            in_flight.0 += 1;
+            in_flight.1 = in_flight.1.max(in_flight.0);
+        }
+        if self.overlap.load(Ordering::SeqCst) {
+            YieldOnce(false).await;
+        }
+        self.in_flight.lock().unwrap().0 -= 1;
+
+        let (status, body) = match reply {
+            Reply::Status(status, body) => (status, body),
+            Reply::Fail(message) => return Err(Error::HttpTransportError(message)),
+            Reply::Hang => {
+                futures_util::future::pending::<()>().await;
+                unreachable!()
+            }
+            Reply::Fixture => {
+                let (status, body) = self.fixture(&request);
+                (status, body.to_string().into_bytes())
+            }
+        };
+        Ok(HttpResponse { status, body })
+    }
+
+    /// The fixture answer for a request. `sendTx` and `execTx` answer with the hash of the
+    /// posted transaction, without checking its signatures.
+    fn fixture(&self, request: &Request) -> (u16, Value) {
+        let (head_block, lib_block) = {
+            let state = self.state.lock().unwrap();
+            (state.head_block, state.lib_block)
+        };
+        let hash = || {
+            let body = request.body.as_deref().unwrap_or_default();
+            serde_json::from_slice::<Tx>(body)
+                .ok()
+                .and_then(|tx| tx.hash().ok())
+                .map(|hash| hash.to_string())
+        };
+        match request.segments().as_slice() {
+            ["sendTx"] => match hash() {
+                Some(hash) => (200, json!({ "hash": hash })),
+                None => (400, fixtures::error(3, "invalid tx json")),
+            },
+            ["execTx"] => match hash() {
+                Some(hash) => (200, fixtures::tx_receipt(&hash)),
+                None => (400, fixtures::error(3, "invalid tx json")),
+            },
+            _ => fixtures::route(&request.path, head_block, lib_block),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for MockTransport {
+    async fn get(&self, url: &str) -> Result<HttpResponse> {
+        self.request("GET", url, None).await
+    }
+
+    async fn post(&self, url: &str, body: Vec<u8>) -> Result<HttpResponse> {
+        self.request("POST", url, Some(body)).await
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        YieldOnce(false).await;
+        self.state.lock().unwrap().sleeps.push(duration);
+    }
+
+    async fn post_stream(&self, url: &str, body: Vec<u8>) -> Result<HttpStreamResponse> {
+        let mut state = self.state.lock().unwrap();
+        state.requests.push(Request {
+            method: "POST",
+            url: url.to_string(),
+            path: path(url),
+            body: Some(body),
+        });
+        if state.streams.is_empty() {
+            return Err(Error::HttpTransportError("connection refused".to_string()));
+        }
+        let (status, chunks) = state.streams.remove(0);
+        let chunks: Vec<Result<Vec<u8>>> = chunks
+            .into_iter()
+            .map(|chunk| Ok(chunk.as_bytes().to_vec()))
+            .collect();
+        Ok(HttpStreamResponse {
+            status,
+            body: Box::pin(stream::iter(chunks)),
+        })
+    }
+}
+
+/// `url` without the scheme and host.
+fn path(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split_once('/')
+        .map_or("", |(_, path)| path)
+        .to_string()
+}
+
+/// Pending on the first poll. Wakes itself, since combinators like `buffered` only poll
+/// futures that were woken.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
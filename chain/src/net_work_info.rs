@@ -1,8 +1,11 @@
 use alloc::string::String;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::json::{JsonObject, NoStdDeserialize};
+
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct NetWork {
@@ -11,3 +14,13 @@ pub struct NetWork {
     /// Peer count of the node
     pub peer_count: i32,
 }
+
+impl NoStdDeserialize for NetWork {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(NetWork {
+            id: object.field("id")?,
+            peer_count: object.field("peer_count")?,
+        })
+    }
+}
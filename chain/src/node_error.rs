@@ -0,0 +1,186 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::{Error, ErrorMessage};
+
+/// What went wrong on the node side of a request, derived from the HTTP status and the
+/// `code`/`message` of the error body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeErrorKind {
+    /// the transaction's expiration has passed, or its time is out of the accepted window
+    TxExpired,
+    /// the transaction is already pending or on chain
+    DuplicateTx,
+    BalanceNotEnough,
+    GasNotEnough,
+    /// the payer can't pay for the RAM the transaction uses
+    RamNotEnough,
+    /// the signatures don't satisfy the permission the transaction needs
+    PermissionDenied,
+    /// the requested transaction, block, account or contract doesn't exist
+    NotFound,
+    /// the request was rejected as malformed
+    InvalidRequest,
+    /// HTTP 429, or the node reports it is throttling
+    RateLimited,
+    /// the node or a gateway in front of it is overloaded or unreachable
+    Unavailable,
+    /// any other failure
+    Unknown,
+}
+
+/// gRPC status codes, which the node's HTTP gateway puts in `ErrorMessage.code`.
+const GRPC_INVALID_ARGUMENT: i32 = 3;
+const GRPC_DEADLINE_EXCEEDED: i32 = 4;
+const GRPC_NOT_FOUND: i32 = 5;
+const GRPC_RESOURCE_EXHAUSTED: i32 = 8;
+const GRPC_UNAVAILABLE: i32 = 14;
+
+impl NodeErrorKind {
+    /// Classify an error response. The message is checked first, because the gateway reports
+    /// most transaction errors as HTTP 500 with the generic code 2.
+    pub fn classify(status: u16, message: &ErrorMessage) -> NodeErrorKind {
+        let text: String = message.message.to_lowercase();
+        let contains = |patterns: &[&str]| patterns.iter().any(|p| text.contains(p));
+
+        if status == 429 || contains(&["rate limit", "too many requests"]) {
+            NodeErrorKind::RateLimited
+        } else if contains(&["duplicate", "duperror", "tx exists", "exists in chain"]) {
+            NodeErrorKind::DuplicateTx
+        } else if contains(&["expired", "expiration", "time error", "timeerror"]) {
+            NodeErrorKind::TxExpired
+        } else if contains(&["balance not enough"]) {
+            NodeErrorKind::BalanceNotEnough
+        } else if contains(&["gas not enough", "gas run out", "out of gas"]) {
+            NodeErrorKind::GasNotEnough
+        } else if contains(&["ram not enough", "pay ram failed"]) {
+            NodeErrorKind::RamNotEnough
+        } else if contains(&[
+            "permission denied",
+            "no permission",
+            "auth failed",
+            "signature error",
+        ]) {
+            NodeErrorKind::PermissionDenied
+        } else if contains(&["is full", "unavailable", "busy"])
+            || status == 502
+            || status == 503
+            || status == 504
+            || message.code == GRPC_UNAVAILABLE
+            || message.code == GRPC_DEADLINE_EXCEEDED
+            || message.code == GRPC_RESOURCE_EXHAUSTED
+        {
+            NodeErrorKind::Unavailable
+        } else if status == 404 || message.code == GRPC_NOT_FOUND || contains(&["not found"]) {
+            NodeErrorKind::NotFound
+        } else if status == 400 || message.code == GRPC_INVALID_ARGUMENT {
+            NodeErrorKind::InvalidRequest
+        } else {
+            NodeErrorKind::Unknown
+        }
+    }
+
+    /// Whether the same request may succeed if sent again later.
+    pub fn is_transient(self) -> bool {
+        matches!(
+            self,
+            NodeErrorKind::RateLimited | NodeErrorKind::Unavailable
+        )
+    }
+}
+
+impl fmt::Display for NodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            NodeErrorKind::TxExpired => "transaction expired",
+            NodeErrorKind::DuplicateTx => "duplicate transaction",
+            NodeErrorKind::BalanceNotEnough => "balance not enough",
+            NodeErrorKind::GasNotEnough => "gas not enough",
+            NodeErrorKind::RamNotEnough => "RAM not enough",
+            NodeErrorKind::PermissionDenied => "permission denied",
+            NodeErrorKind::NotFound => "not found",
+            NodeErrorKind::InvalidRequest => "invalid request",
+            NodeErrorKind::RateLimited => "rate limited",
+            NodeErrorKind::Unavailable => "unavailable",
+            NodeErrorKind::Unknown => "unknown",
+        })
+    }
+}
+
+/// Whether a failed request is worth retrying: transient node errors and transport failures.
+pub fn is_transient(err: &Error) -> bool {
+    match err {
+        Error::NodeError(kind, _) => kind.is_transient(),
+        #[cfg(feature = "client")]
+        Error::Reqwest(err) => !err.is_decode() && !err.is_builder(),
+        Error::HttpTransportError(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn kind(status: u16, code: i32, message: &str) -> NodeErrorKind {
+        NodeErrorKind::classify(
+            status,
+            &ErrorMessage {
+                code,
+                message: message.to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn should_classify_error_messages() {
+        assert_eq!(kind(500, 2, "tx err:DupError"), NodeErrorKind::DuplicateTx);
+        assert_eq!(kind(500, 2, "tx is expired"), NodeErrorKind::TxExpired);
+        assert_eq!(
+            kind(500, 2, "balance not enough: 10 < 100"),
+            NodeErrorKind::BalanceNotEnough
+        );
+        assert_eq!(
+            kind(500, 2, "pay ram failed: ram not enough"),
+            NodeErrorKind::RamNotEnough
+        );
+        assert_eq!(
+            kind(500, 2, "transaction has no permission"),
+            NodeErrorKind::PermissionDenied
+        );
+        assert_eq!(kind(429, 0, ""), NodeErrorKind::RateLimited);
+        assert_eq!(kind(503, 0, ""), NodeErrorKind::Unavailable);
+        assert_eq!(
+            kind(500, 14, "connection refused"),
+            NodeErrorKind::Unavailable
+        );
+        assert_eq!(kind(500, 5, "tx not found"), NodeErrorKind::NotFound);
+        assert_eq!(kind(400, 3, "bad hash"), NodeErrorKind::InvalidRequest);
+        assert_eq!(kind(500, 2, "something"), NodeErrorKind::Unknown);
+    }
+
+    #[test]
+    fn should_only_retry_transient_errors() {
+        assert!(NodeErrorKind::RateLimited.is_transient());
+        assert!(NodeErrorKind::Unavailable.is_transient());
+        assert!(!NodeErrorKind::TxExpired.is_transient());
+        assert!(!NodeErrorKind::DuplicateTx.is_transient());
+        assert!(is_transient(&Error::HttpTransportError(
+            "reset".to_string()
+        )));
+        assert!(!is_transient(&Error::JsonParserError()));
+    }
+
+    #[test]
+    fn should_expose_kind_on_error() {
+        let err =
+            crate::client::error_response(500, br#"{"code": 2, "message": "tx err:DupError"}"#);
+        assert_eq!(err.node_error_kind(), Some(NodeErrorKind::DuplicateTx));
+        assert_eq!(
+            err.to_string(),
+            "node error (duplicate transaction) 2: tx err:DupError"
+        );
+        assert_eq!(Error::JsonParserError().node_error_kind(), None);
+    }
+}
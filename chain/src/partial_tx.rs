@@ -0,0 +1,450 @@
+//! Partially signed transactions, for collecting the signatures of several parties, e.g. on
+//! air-gapped machines, before a transaction is sent.
+//!
+//! A [`PartialTx`] holds the unsigned body of a transaction, whose `signers` and `publisher`
+//! name who is expected to sign, and the signatures collected so far, each attributed to its
+//! signer. Every signature is checked against the body when it is added or merged. Signers
+//! sign first; the publisher signs once all of them have, since its signature covers theirs.
+//!
+//! It travels as JSON (with `std`) or in a binary form, both carrying [`PARTIAL_TX_VERSION`].
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::tx_decode::{invalid, Decoder};
+use crate::{Error, Result, Signature, Tx, TxSigner};
+
+/// Version of the encodings of `PartialTx` written by this crate.
+pub const PARTIAL_TX_VERSION: u32 = 1;
+
+/// First bytes of the binary form.
+const MAGIC: &[u8; 4] = b"IPTX";
+
+/// A signature of one of the signers of a transaction.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SignerSignature {
+    /// `account@permission`, as declared in `Tx.signers`
+    pub signer: String,
+    pub signature: Signature,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PartialTx {
+    pub version: u32,
+    /// the transaction, without signatures
+    pub tx: Tx,
+    pub signatures: Vec<SignerSignature>,
+    pub publisher_sigs: Vec<Signature>,
+}
+
+/// What a `PartialTx` still needs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartialTxStatus {
+    pub publisher: String,
+    /// declared signers with at least one signature
+    pub signed: Vec<String>,
+    /// declared signers without a signature
+    pub missing: Vec<String>,
+    pub publisher_signed: bool,
+}
+
+impl PartialTxStatus {
+    /// Whether `finalize` can succeed.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.publisher_signed
+    }
+}
+
+impl PartialTx {
+    /// Start collecting signatures for `tx`, which names its publisher and signers and must
+    /// not be signed yet.
+    pub fn new(tx: Tx) -> Result<Self> {
+        if tx.publisher.is_empty() {
+            return Err(Error::InvalidTx("no publisher".to_string()));
+        }
+        if !tx.signatures.is_empty() || !tx.publisher_sigs.is_empty() {
+            return Err(Error::InvalidTx("already signed".to_string()));
+        }
+        Ok(PartialTx {
+            version: PARTIAL_TX_VERSION,
+            tx,
+            signatures: Vec::new(),
+            publisher_sigs: Vec::new(),
+        })
+    }
+
+    /// Add the signature of the declared `signer` from `key`.
+    pub async fn sign_as_signer(&mut self, signer: &str, key: &dyn TxSigner) -> Result<()> {
+        let digest = self.tx.digest(false)?;
+        let signature = key.sign(&digest).await?;
+        self.add_signature(SignerSignature {
+            signer: signer.to_string(),
+            signature,
+        })
+    }
+
+    /// Add a signature of the publisher from `key`, once every signer has signed.
+    pub async fn sign_as_publisher(&mut self, key: &dyn TxSigner) -> Result<()> {
+        let status = self.inspect();
+        if !status.missing.is_empty() {
+            return Err(Error::InvalidTx(format!(
+                "{} must sign before the publisher",
+                status.missing.join(", ")
+            )));
+        }
+        let digest = self.assemble().digest(true)?;
+        let signature = key.sign(&digest).await?;
+        self.publisher_sigs.push(signature);
+        Ok(())
+    }
+
+    /// Add the signatures collected in `other`, a copy of the same transaction.
+    pub fn merge(&mut self, other: &PartialTx) -> Result<()> {
+        if other.tx.publisher != self.tx.publisher
+            || other.tx.customized_to_serialize_data(false)?
+                != self.tx.customized_to_serialize_data(false)?
+        {
+            return Err(Error::InvalidTx(
+                "merging signatures of a different transaction".to_string(),
+            ));
+        }
+        let mut merged = self.clone();
+        for signature in &other.signatures {
+            merged.add_signature(signature.clone())?;
+        }
+        for signature in &other.publisher_sigs {
+            if !merged
+                .publisher_sigs
+                .iter()
+                .any(|known| known.signature == signature.signature)
+            {
+                merged.publisher_sigs.push(signature.clone());
+            }
+        }
+        merged.check_publisher_sigs()?;
+        *self = merged;
+        Ok(())
+    }
+
+    /// Who has signed, and who still has to.
+    pub fn inspect(&self) -> PartialTxStatus {
+        let (signed, missing) = self
+            .tx
+            .signers
+            .iter()
+            .cloned()
+            .partition(|signer| self.signatures.iter().any(|sig| &sig.signer == signer));
+        PartialTxStatus {
+            publisher: self.tx.publisher.clone(),
+            signed,
+            missing,
+            publisher_signed: !self.publisher_sigs.is_empty(),
+        }
+    }
+
+    /// The signed transaction, once every signer and the publisher have signed and all
+    /// signatures are valid.
+    pub fn finalize(&self) -> Result<Tx> {
+        let status = self.inspect();
+        if !status.missing.is_empty() {
+            return Err(Error::MissingSignerSignature());
+        }
+        if !status.publisher_signed {
+            return Err(Error::InvalidTx("publisher hasn't signed".to_string()));
+        }
+        let tx = self.assemble();
+        tx.verify()?;
+        Ok(tx)
+    }
+
+    fn add_signature(&mut self, signature: SignerSignature) -> Result<()> {
+        if !self.tx.signers.contains(&signature.signer) {
+            return Err(Error::InvalidTx(format!(
+                "{} is not a signer",
+                signature.signer
+            )));
+        }
+        if self.signatures.iter().any(|known| {
+            known.signer == signature.signer
+                && known.signature.signature == signature.signature.signature
+        }) {
+            return Ok(());
+        }
+        if !self.publisher_sigs.is_empty() {
+            return Err(Error::InvalidTx(format!(
+                "{} signed after the publisher",
+                signature.signer
+            )));
+        }
+        if !is_valid(&signature.signature, &self.tx.digest(false)?) {
+            return Err(Error::InvalidSignature());
+        }
+        self.signatures.push(signature);
+        Ok(())
+    }
+
+    /// Check the publisher signatures against the signatures of the signers.
+    fn check_publisher_sigs(&self) -> Result<()> {
+        if self.publisher_sigs.is_empty() {
+            return Ok(());
+        }
+        if !self.inspect().missing.is_empty() {
+            return Err(Error::MissingSignerSignature());
+        }
+        let digest = self.assemble().digest(true)?;
+        if !self
+            .publisher_sigs
+            .iter()
+            .all(|signature| is_valid(signature, &digest))
+        {
+            return Err(Error::InvalidPublisherSignature());
+        }
+        Ok(())
+    }
+
+    /// The transaction with the collected signatures. Signer signatures are ordered by the
+    /// declaration of their signer, then by public key, so every party signing as publisher
+    /// signs the same bytes.
+    fn assemble(&self) -> Tx {
+        let mut signatures: Vec<&SignerSignature> = self.signatures.iter().collect();
+        signatures.sort_by_key(|sig| {
+            let declared = self
+                .tx
+                .signers
+                .iter()
+                .position(|signer| signer == &sig.signer);
+            (
+                declared,
+                sig.signature.public_key.clone(),
+                sig.signature.signature.clone(),
+            )
+        });
+        let mut tx = self.tx.clone();
+        tx.signatures = signatures
+            .into_iter()
+            .map(|sig| sig.signature.clone())
+            .collect();
+        tx.publisher_sigs = self.publisher_sigs.clone();
+        tx
+    }
+
+    /// Binary form: `IPTX`, the version, the unsigned transaction, the publisher, then the
+    /// signer and publisher signatures, in the length-prefixed encoding of transactions.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&self.version.to_be_bytes());
+        put_bytes(&mut bytes, &self.tx.customized_to_serialize_data(false)?);
+        put_bytes(&mut bytes, self.tx.publisher.as_bytes());
+        put_len(&mut bytes, self.signatures.len());
+        for signature in &self.signatures {
+            let mut item = Vec::new();
+            put_bytes(&mut item, signature.signer.as_bytes());
+            put_bytes(&mut item, &signature.signature.to_bytes()?);
+            put_bytes(&mut bytes, &item);
+        }
+        put_len(&mut bytes, self.publisher_sigs.len());
+        for signature in &self.publisher_sigs {
+            put_bytes(&mut bytes, &signature.to_bytes()?);
+        }
+        Ok(bytes)
+    }
+
+    /// Read the binary form, checking every signature.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if !bytes.starts_with(MAGIC) {
+            return Err(invalid("not a partially signed transaction"));
+        }
+        let mut decoder = Decoder::new(&bytes[MAGIC.len()..]);
+        let version = decoder.i32()? as u32;
+        check_version(version)?;
+        let mut tx = Tx::decode(&decoder.bytes()?, false)?;
+        tx.publisher = decoder.string()?;
+        let signatures = decoder.items(|item| {
+            let signer = item.string()?;
+            let signature = Signature::from_bytes(&item.bytes()?)?;
+            Ok(SignerSignature { signer, signature })
+        })?;
+        let publisher_sigs = decoder.items(Signature::decode)?;
+        decoder.finish()?;
+        Self::checked(PartialTx {
+            version,
+            tx,
+            signatures,
+            publisher_sigs,
+        })
+    }
+
+    #[cfg(feature = "std")]
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|_| Error::JsonParserError())
+    }
+
+    /// Read the JSON form, checking every signature.
+    #[cfg(feature = "std")]
+    pub fn from_json(json: &str) -> Result<Self> {
+        let partial: PartialTx =
+            serde_json::from_str(json).map_err(|_| Error::JsonParserError())?;
+        check_version(partial.version)?;
+        Self::checked(partial)
+    }
+
+    /// Re-add the signatures of a decoded `PartialTx`, so each is checked.
+    fn checked(decoded: PartialTx) -> Result<Self> {
+        let mut partial = PartialTx::new(decoded.tx)?;
+        for signature in decoded.signatures {
+            partial.add_signature(signature)?;
+        }
+        partial.publisher_sigs = decoded.publisher_sigs;
+        partial.check_publisher_sigs()?;
+        Ok(partial)
+    }
+}
+
+/// Whether `signature` is well formed and signs `digest`.
+fn is_valid(signature: &Signature, digest: &[u8]) -> bool {
+    signature.to_bytes().is_ok() && signature.verify(digest)
+}
+
+fn check_version(version: u32) -> Result<()> {
+    if version != PARTIAL_TX_VERSION {
+        return Err(invalid(format!(
+            "unsupported partially signed transaction version {}",
+            version
+        )));
+    }
+    Ok(())
+}
+
+fn put_len(bytes: &mut Vec<u8>, len: usize) {
+    bytes.extend_from_slice(&(len as u32).to_be_bytes());
+}
+
+fn put_bytes(bytes: &mut Vec<u8>, item: &[u8]) {
+    put_len(bytes, item.len());
+    bytes.extend_from_slice(item);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transport::block_on;
+    use crate::{IostAction, KeySigner};
+    use alloc::vec;
+
+    fn alice() -> KeySigner {
+        KeySigner::ed25519(&base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap()).unwrap()
+    }
+
+    fn bob() -> KeySigner {
+        KeySigner::secp256k1(&[7u8; 32]).unwrap()
+    }
+
+    fn carol() -> KeySigner {
+        KeySigner::secp256k1(&[9u8; 32]).unwrap()
+    }
+
+    fn partial() -> PartialTx {
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![IostAction::transfer("alice", "bob", "10", "").unwrap()],
+        );
+        tx.publisher = "alice".to_string();
+        tx.add_signer("bob@active").unwrap();
+        tx.add_signer("carol@active").unwrap();
+        PartialTx::new(tx).unwrap()
+    }
+
+    #[test]
+    fn should_collect_merge_and_finalize() {
+        let unsigned = partial();
+        assert_eq!(
+            unsigned.inspect().missing,
+            vec!["bob@active".to_string(), "carol@active".to_string()]
+        );
+
+        // each signer signs a copy carried over in a different encoding
+        let mut from_bob = PartialTx::from_bytes(&unsigned.to_bytes().unwrap()).unwrap();
+        block_on(from_bob.sign_as_signer("bob@active", &bob())).unwrap();
+        let mut from_carol = PartialTx::from_json(&unsigned.to_json().unwrap()).unwrap();
+        block_on(from_carol.sign_as_signer("carol@active", &carol())).unwrap();
+        assert!(block_on(from_carol.sign_as_publisher(&alice())).is_err());
+
+        let mut merged = from_carol.clone();
+        merged.merge(&from_bob).unwrap();
+        merged.merge(&from_bob).unwrap();
+        assert_eq!(merged.signatures.len(), 2);
+        assert!(matches!(merged.finalize(), Err(Error::InvalidTx(_))));
+        block_on(merged.sign_as_publisher(&alice())).unwrap();
+        let status = merged.inspect();
+        assert!(status.is_complete());
+        assert_eq!(status.publisher, "alice");
+
+        // merging in the other order gives the same transaction
+        let mut other = from_bob.clone();
+        other.merge(&from_carol).unwrap();
+        other.merge(&merged).unwrap();
+        let tx = merged.finalize().unwrap();
+        assert_eq!(
+            tx.hash().unwrap(),
+            other.finalize().unwrap().hash().unwrap()
+        );
+        assert!(tx.verify().is_ok());
+        assert_eq!(
+            tx.signatures[0].public_key,
+            base64::encode(bob().public_key())
+        );
+
+        let decoded = PartialTx::from_bytes(&merged.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            decoded.finalize().unwrap().hash().unwrap(),
+            tx.hash().unwrap()
+        );
+        let decoded = PartialTx::from_json(&merged.to_json().unwrap()).unwrap();
+        assert_eq!(
+            decoded.finalize().unwrap().hash().unwrap(),
+            tx.hash().unwrap()
+        );
+
+        // signer signatures can't be added once the publisher signed
+        let mut late = from_bob.clone();
+        block_on(late.sign_as_signer("carol@active", &carol())).unwrap();
+        block_on(late.sign_as_publisher(&alice())).unwrap();
+        assert!(block_on(late.sign_as_signer("carol@active", &bob())).is_err());
+    }
+
+    #[test]
+    fn should_reject_invalid_signatures() {
+        let mut partial = partial();
+        assert!(block_on(partial.sign_as_signer("dave@active", &bob())).is_err());
+        block_on(partial.sign_as_signer("bob@active", &bob())).unwrap();
+
+        let mut forged = partial.clone();
+        forged.signatures[0].signature.signature = base64::encode([1u8; 64]);
+        assert!(PartialTx::from_json(&forged.to_json().unwrap()).is_err());
+        assert!(PartialTx::from_bytes(&forged.to_bytes().unwrap()).is_err());
+
+        let mut changed = partial.clone();
+        changed.tx.gas_limit = 2_000_000.0;
+        assert!(changed.merge(&partial).is_err());
+
+        let mut bytes = partial.to_bytes().unwrap();
+        bytes[7] = 2;
+        assert!(matches!(
+            PartialTx::from_bytes(&bytes),
+            Err(Error::TxDecodeError(_))
+        ));
+        let json = partial
+            .to_json()
+            .unwrap()
+            .replace("\"version\":1", "\"version\":2");
+        assert!(PartialTx::from_json(&json).is_err());
+    }
+}
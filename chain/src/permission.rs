@@ -2,6 +2,8 @@ use alloc::string::String;
 use alloc::vec::Vec;
 
 use crate::item::Item;
+use crate::json::{JsonObject, NoStdDeserialize};
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
@@ -17,3 +19,15 @@ pub struct Permission {
     /// permission threshold
     pub threshold: String,
 }
+
+impl NoStdDeserialize for Permission {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(Permission {
+            name: object.field("name")?,
+            group_names: object.field("group_names")?,
+            items: object.field("items")?,
+            threshold: object.field("threshold")?,
+        })
+    }
+}
@@ -1,8 +1,11 @@
 use alloc::string::String;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::json::{JsonObject, NoStdDeserialize};
+
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct PledgeInfo {
@@ -11,3 +14,13 @@ pub struct PledgeInfo {
     /// 	the amount of the deposit
     pub amount: f64,
 }
+
+impl NoStdDeserialize for PledgeInfo {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(PledgeInfo {
+            pledger: object.field("pledger")?,
+            amount: object.field("amount")?,
+        })
+    }
+}
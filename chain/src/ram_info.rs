@@ -1,8 +1,11 @@
 use alloc::string::String;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::json::{JsonObject, NoStdDeserialize};
+
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct RAMInfo {
@@ -13,3 +16,14 @@ pub struct RAMInfo {
     /// RAM bytes total
     pub total: String,
 }
+
+impl NoStdDeserialize for RAMInfo {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(RAMInfo {
+            available: object.field("available")?,
+            used: object.field("used")?,
+            total: object.field("total")?,
+        })
+    }
+}
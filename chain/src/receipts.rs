@@ -1,15 +1,32 @@
 use alloc::string::String;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::Error;
+
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Receipt {
     /// ABI function name
-    #[serde(rename = "funcName")]
-    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "std", serde(rename = "funcName", alias = "func_name"))]
     pub func_name: String,
     /// content
     pub content: String,
 }
+
+impl NoStdDeserialize for Receipt {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        let func_name = object
+            .get("funcName")
+            .or_else(|| object.get("func_name"))
+            .ok_or(Error::JsonParserError())?;
+        Ok(Receipt {
+            func_name: String::no_std_deserialize(func_name)?,
+            content: object.field("content")?,
+        })
+    }
+}
@@ -0,0 +1,108 @@
+use core::time::Duration;
+
+/// Exponential backoff for requests that fail with a transient error, see
+/// [`node_error::is_transient`](crate::node_error::is_transient).
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// retries after the first attempt; 0 disables retrying
+    pub max_retries: u32,
+    /// wait before the first retry
+    pub initial_backoff: Duration,
+    /// upper bound for a single wait
+    pub max_backoff: Duration,
+    /// factor applied to the wait after every retry
+    pub multiplier: u32,
+    /// bound on the total time spent on a request, retries and waits included. An attempt
+    /// still running at the deadline is abandoned with `Error::RequestTimeout`.
+    /// Only enforced with the `std` feature, which provides a clock.
+    pub deadline: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(2),
+            multiplier: 2,
+            deadline: Some(Duration::from_secs(10)),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// a policy that sends every request exactly once
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            deadline: None,
+            ..Self::default()
+        }
+    }
+
+    /// Wait before retry number `retry`, counting from 0.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let mut backoff = self.initial_backoff;
+        for _ in 0..retry {
+            backoff = backoff
+                .checked_mul(self.multiplier)
+                .unwrap_or(self.max_backoff);
+            if backoff >= self.max_backoff {
+                break;
+            }
+        }
+        backoff.min(self.max_backoff)
+    }
+}
+
+/// Start time of a request, for enforcing [`RetryPolicy::deadline`].
+pub(crate) struct RetryTimer {
+    #[cfg(feature = "std")]
+    started: std::time::Instant,
+}
+
+impl RetryTimer {
+    pub fn start() -> Self {
+        RetryTimer {
+            #[cfg(feature = "std")]
+            started: std::time::Instant::now(),
+        }
+    }
+
+    /// Whether waiting `backoff` and trying again still fits in the deadline.
+    #[cfg(feature = "std")]
+    pub fn allows(&self, policy: &RetryPolicy, backoff: Duration) -> bool {
+        match policy.deadline {
+            Some(deadline) => self.started.elapsed() + backoff < deadline,
+            None => true,
+        }
+    }
+
+    /// Time left before the deadline, if there is one.
+    #[cfg(feature = "std")]
+    pub fn remaining(&self, policy: &RetryPolicy) -> Option<Duration> {
+        policy
+            .deadline
+            .map(|deadline| deadline.saturating_sub(self.started.elapsed()))
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn allows(&self, _policy: &RetryPolicy, _backoff: Duration) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_back_off_exponentially() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0), Duration::from_millis(200));
+        assert_eq!(policy.backoff(1), Duration::from_millis(400));
+        assert_eq!(policy.backoff(2), Duration::from_millis(800));
+        assert_eq!(policy.backoff(4), Duration::from_secs(2));
+        assert_eq!(policy.backoff(100), Duration::from_secs(2));
+    }
+}
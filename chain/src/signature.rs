@@ -1,9 +1,12 @@
 #![allow(unconditional_recursion)]
+use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::{Error, NumberBytes, Read, Write};
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::tx_decode::{invalid, Decoder};
+use crate::{Error, KeySigner, NumberBytes, Read, Write};
 use core::str::FromStr;
 use keys::algorithm;
 use lite_json::JsonValue;
@@ -24,21 +27,68 @@ pub struct Signature {
 
 impl Signature {
     pub fn sign(message: &[u8], sign_algorithm: &str, sec_key: &[u8]) -> crate::Result<Signature> {
-        let algorithm = algorithm::new(sign_algorithm);
-        let pub_key = algorithm.get_pub_key(sec_key).unwrap();
-        let result = algorithm.sign(message, sec_key);
+        KeySigner::new(sign_algorithm, sec_key)?.sign_digest(message)
+    }
+
+    /// Algorithm byte of the go-iost encoding: 1 for secp256k1, 2 for ed25519.
+    pub fn algorithm_byte(&self) -> crate::Result<u8> {
+        match algorithm::normalize(&self.algorithm) {
+            Some(algorithm::SECP256K1) => Ok(1),
+            Some(_) => Ok(2),
+            None => Err(Error::UnsupportedAlgorithm(self.algorithm.clone())),
+        }
+    }
+
+    /// Binary encoding of go-iost: the algorithm byte, then the raw signature and public key,
+    /// each prefixed with its length. A transaction signed by its publisher covers the
+    /// signatures of its signers in this form.
+    pub fn to_bytes(&self) -> crate::Result<Vec<u8>> {
+        let algorithm = self.algorithm_byte()?;
+        let signature =
+            base64::decode(self.signature.as_str()).map_err(|_| Error::InvalidSignature())?;
+        let public_key =
+            base64::decode(self.public_key.as_str()).map_err(|_| Error::InvalidSignature())?;
+        let mut bytes = vec![0u8; 1 + signature.num_bytes() + public_key.num_bytes()];
+        let pos = &mut 0;
+        algorithm
+            .write(&mut bytes, pos)
+            .and_then(|_| signature.write(&mut bytes, pos))
+            .and_then(|_| public_key.write(&mut bytes, pos))
+            .map_err(Error::BytesWriteError)?;
+        Ok(bytes)
+    }
+
+    /// Inverse of `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Signature> {
+        let mut decoder = Decoder::new(bytes);
+        let signature = Self::decode(&mut decoder)?;
+        decoder.finish()?;
+        Ok(signature)
+    }
+
+    pub(crate) fn decode(decoder: &mut Decoder) -> crate::Result<Signature> {
+        let algorithm = match decoder.u8()? {
+            1 => algorithm::SECP256K1,
+            2 => algorithm::ED25519,
+            other => return Err(invalid(format!("unknown signature algorithm {}", other))),
+        };
         Ok(Signature {
-            algorithm: sign_algorithm.to_string(),
-            signature: base64::encode(result),
-            public_key: base64::encode(pub_key),
+            algorithm: algorithm.to_string(),
+            signature: base64::encode(decoder.bytes()?),
+            public_key: base64::encode(decoder.bytes()?),
         })
     }
 
+    /// Whether this is a valid signature of `message`. False for unknown algorithms.
     pub fn verify(&self, message: &[u8]) -> bool {
-        let algorithm = algorithm::new(self.algorithm.as_str());
-        let pub_key = base64::decode(self.public_key.as_str()).unwrap();
-        let sig = base64::decode(self.signature.as_str()).unwrap();
-        algorithm.verify(message, pub_key.as_slice(), sig.as_slice())
+        match (
+            algorithm::try_new(self.algorithm.as_str()),
+            base64::decode(self.public_key.as_str()),
+            base64::decode(self.signature.as_str()),
+        ) {
+            (Ok(algorithm), Ok(pub_key), Ok(sig)) => algorithm.verify(message, &pub_key, &sig),
+            _ => false,
+        }
     }
 
     pub fn no_std_serialize(&self) -> JsonValue {
@@ -61,6 +111,17 @@ impl Signature {
     }
 }
 
+impl NoStdDeserialize for Signature {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(Signature {
+            algorithm: object.field_or_default("algorithm")?,
+            signature: object.field_or_default("signature")?,
+            public_key: object.field_or_default("public_key")?,
+        })
+    }
+}
+
 #[cfg(feature = "std")]
 impl<'de> serde::Deserialize<'de> for Signature {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
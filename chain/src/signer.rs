@@ -0,0 +1,282 @@
+//! Signing through [`TxSigner`], so keys don't have to be in process memory: implement it for a
+//! remote signing service or an HSM, or use the in-memory [`KeySigner`].
+
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+
+use async_trait::async_trait;
+use keys::algorithm;
+
+use crate::{Error, Result, Signature};
+
+/// Signs the SHA3-256 digests of transactions with one key.
+///
+/// Signing is async, so a signer backed by a remote service or an HSM waits for its answer
+/// without blocking the executor, and signers can be shared between tasks.
+#[async_trait]
+pub trait TxSigner: Send + Sync {
+    /// `algorithm::ED25519` or `algorithm::SECP256K1`
+    fn algorithm(&self) -> &str;
+
+    /// Raw public key of the signing key.
+    fn public_key(&self) -> Vec<u8>;
+
+    /// Sign `digest`, returning the signature with the algorithm and public key filled in.
+    async fn sign(&self, digest: &[u8]) -> Result<Signature>;
+}
+
+/// Signer holding a secret key in memory.
+#[derive(Clone)]
+pub struct KeySigner {
+    algorithm: &'static str,
+    sec_key: Vec<u8>,
+    public_key: Vec<u8>,
+}
+
+impl KeySigner {
+    /// Signer for `sec_key` of `sign_algorithm`, `algorithm::ED25519` or
+    /// `algorithm::SECP256K1` in any case.
+    pub fn new(sign_algorithm: &str, sec_key: &[u8]) -> Result<Self> {
+        let algorithm = algorithm::normalize(sign_algorithm)
+            .ok_or_else(|| Error::UnsupportedAlgorithm(sign_algorithm.to_string()))?;
+        let public_key = algorithm::try_new(algorithm)
+            .and_then(|algorithm| algorithm.get_pub_key(sec_key))
+            .map_err(Error::KeyError)?;
+        Ok(KeySigner {
+            algorithm,
+            sec_key: sec_key.to_vec(),
+            public_key,
+        })
+    }
+
+    /// Signer for a 64 byte ed25519 key pair, the secret key followed by the public key.
+    pub fn ed25519(sec_key: &[u8]) -> Result<Self> {
+        Self::new(algorithm::ED25519, sec_key)
+    }
+
+    /// Signer for a 32 byte secp256k1 secret key.
+    pub fn secp256k1(sec_key: &[u8]) -> Result<Self> {
+        Self::new(algorithm::SECP256K1, sec_key)
+    }
+
+    /// Sign `digest` right away, like [`TxSigner::sign`].
+    pub fn sign_digest(&self, digest: &[u8]) -> Result<Signature> {
+        let signature = algorithm::try_new(self.algorithm)
+            .map_err(Error::KeyError)?
+            .sign(digest, &self.sec_key);
+        Ok(Signature {
+            algorithm: self.algorithm.to_string(),
+            signature: base64::encode(signature),
+            public_key: base64::encode(&self.public_key),
+        })
+    }
+}
+
+#[async_trait]
+impl TxSigner for KeySigner {
+    fn algorithm(&self) -> &str {
+        self.algorithm
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    async fn sign(&self, digest: &[u8]) -> Result<Signature> {
+        self.sign_digest(digest)
+    }
+}
+
+/// Shows the public key only.
+impl fmt::Debug for KeySigner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeySigner")
+            .field("algorithm", &self.algorithm)
+            .field("public_key", &base64::encode(&self.public_key))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transport::block_on;
+    use crate::{IostAction, Tx};
+    use alloc::format;
+    use alloc::vec;
+    use std::sync::Mutex;
+
+    const ED25519_KEY: &str =
+        "gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==";
+
+    /// Signer that keeps its key elsewhere and records what it was asked to sign.
+    struct Remote {
+        key: KeySigner,
+        requests: Mutex<Vec<Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl TxSigner for Remote {
+        fn algorithm(&self) -> &str {
+            self.key.algorithm()
+        }
+
+        fn public_key(&self) -> Vec<u8> {
+            self.key.public_key()
+        }
+
+        async fn sign(&self, digest: &[u8]) -> Result<Signature> {
+            self.requests.lock().unwrap().push(digest.to_vec());
+            self.key.sign(digest).await
+        }
+    }
+
+    /// Signer answering with the signature of another key, or with a corrupted one.
+    struct Faulty {
+        key: KeySigner,
+        signs_with: KeySigner,
+        corrupt: bool,
+    }
+
+    #[async_trait]
+    impl TxSigner for Faulty {
+        fn algorithm(&self) -> &str {
+            self.key.algorithm()
+        }
+
+        fn public_key(&self) -> Vec<u8> {
+            self.key.public_key()
+        }
+
+        async fn sign(&self, digest: &[u8]) -> Result<Signature> {
+            let mut signature = self.signs_with.sign_digest(digest)?;
+            if self.corrupt {
+                signature.signature = base64::encode([1u8; 64]);
+            }
+            Ok(signature)
+        }
+    }
+
+    fn transfer() -> Tx {
+        Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![IostAction::transfer("alice", "bob", "10", "").unwrap()],
+        )
+    }
+
+    #[test]
+    fn should_sign_through_signers() {
+        let ed25519 = KeySigner::ed25519(&base64::decode(ED25519_KEY).unwrap()).unwrap();
+        let secp256k1 = KeySigner::secp256k1(&[7u8; 32]).unwrap();
+        assert_eq!(secp256k1.algorithm(), algorithm::SECP256K1);
+        assert_eq!(secp256k1.public_key().len(), 33);
+        let remote = Remote {
+            key: secp256k1,
+            requests: Mutex::new(vec![]),
+        };
+
+        let mut tx = transfer();
+        tx.add_signer("bob@active").unwrap();
+        block_on(tx.sign_as_signer_with("bob@active", &remote)).unwrap();
+        block_on(tx.sign_with("alice", &ed25519)).unwrap();
+        assert!(tx.verify().is_ok());
+        assert_eq!(tx.publisher, "alice");
+        assert_eq!(tx.signatures[0].algorithm, algorithm::SECP256K1);
+        let requests = remote.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].len(), 32);
+        // signing can run on a multi-threaded executor
+        fn is_send<T: Send>(_: &T) {}
+        is_send(&tx.clone().sign_with("alice", &ed25519));
+
+        // the key based methods sign the same way
+        let mut other = transfer();
+        other.add_signer("bob@active").unwrap();
+        other
+            .sign_as_signer("bob@active", "secp256k1", &[7u8; 32])
+            .unwrap();
+        other
+            .sign(
+                "alice".to_string(),
+                algorithm::ED25519,
+                &base64::decode(ED25519_KEY).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            other.customized_to_serialize_data(true).unwrap(),
+            tx.customized_to_serialize_data(true).unwrap()
+        );
+        assert_eq!(
+            other.publisher_sigs[0].signature,
+            tx.publisher_sigs[0].signature
+        );
+    }
+
+    #[test]
+    fn should_reject_faulty_signers() {
+        let ed25519 = KeySigner::ed25519(&base64::decode(ED25519_KEY).unwrap()).unwrap();
+        let secp256k1 = KeySigner::secp256k1(&[7u8; 32]).unwrap();
+        let other = KeySigner::secp256k1(&[8u8; 32]).unwrap();
+        let faulty = [
+            (ed25519.clone(), secp256k1.clone(), false),
+            (secp256k1.clone(), other, false),
+            (
+                ed25519,
+                KeySigner::ed25519(&base64::decode(ED25519_KEY).unwrap()).unwrap(),
+                true,
+            ),
+        ];
+        for (key, signs_with, corrupt) in faulty.iter().cloned() {
+            let signer = Faulty {
+                key,
+                signs_with,
+                corrupt,
+            };
+            let mut tx = transfer();
+            tx.add_signer("bob@active").unwrap();
+            assert!(matches!(
+                block_on(tx.sign_as_signer_with("bob@active", &signer)),
+                Err(Error::InvalidSignature())
+            ));
+            assert!(matches!(
+                block_on(tx.sign_with("alice", &signer)),
+                Err(Error::InvalidSignature())
+            ));
+            assert!(tx.signatures.is_empty() && tx.publisher_sigs.is_empty());
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_keys() {
+        assert!(matches!(
+            KeySigner::new("RSA", &[7u8; 32]),
+            Err(Error::UnsupportedAlgorithm(_))
+        ));
+        assert!(matches!(
+            KeySigner::ed25519(&[7u8; 32]),
+            Err(Error::KeyError(_))
+        ));
+        assert!(matches!(
+            KeySigner::secp256k1(&[0u8; 32]),
+            Err(Error::KeyError(_))
+        ));
+        let signature = Signature {
+            algorithm: "RSA".to_string(),
+            ..KeySigner::secp256k1(&[7u8; 32])
+                .unwrap()
+                .sign_digest(&[0u8; 32])
+                .unwrap()
+        };
+        assert!(matches!(
+            signature.algorithm_byte(),
+            Err(Error::UnsupportedAlgorithm(_))
+        ));
+        assert!(!signature.verify(&[0u8; 32]));
+        let debug = format!("{:?}", KeySigner::secp256k1(&[7u8; 32]).unwrap());
+        assert!(!debug.contains(&format!("{:?}", [7u8; 32])));
+    }
+}
@@ -6,7 +6,7 @@ use base64;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use keys::algorithm;
+use keys::algorithm::{Algorithm, AlgorithmEd25519};
 
 use crate::spv::{Head, Sign, Tx};
 use crate::Error::IOSTBlockVerifyError;
@@ -41,7 +41,7 @@ pub struct Block {
 impl Block {
     #[cfg(feature = "std")]
     pub(crate) fn verify_self(&self) -> Result<()> {
-        let ed25519 = algorithm::new(algorithm::ED25519);
+        let ed25519 = AlgorithmEd25519;
         let sign = base64::decode(self.sign.sig.as_str()).unwrap();
         let pub_key = bs58::decode(self.head.witness.as_str()).into_vec().unwrap();
         let hash = self.head.hash();
@@ -6,7 +6,7 @@ use base64;
 use serde::{Deserialize, Deserializer, Serialize};
 use sha3::{Digest, Sha3_256};
 
-use keys::algorithm;
+use keys::algorithm::{Algorithm, AlgorithmEd25519};
 
 use crate::spv::Sign;
 use crate::verify::BlockHead;
@@ -59,7 +59,7 @@ impl Head {
     }
 
     pub fn verify(&self, sign: Sign) -> bool {
-        let ed25519 = algorithm::new(algorithm::ED25519);
+        let ed25519 = AlgorithmEd25519;
         let sign = base64::decode(sign.sig.as_str()).unwrap();
         let pub_key = bs58::decode(self.witness.as_str()).into_vec().unwrap();
         ed25519.verify(self.hash().as_slice(), pub_key.as_slice(), sign.as_slice())
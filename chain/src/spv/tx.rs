@@ -58,7 +58,7 @@ pub struct Tx {
     pub reserved: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct TxReceiptStatus {
     pub code: i32,
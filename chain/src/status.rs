@@ -1,7 +1,13 @@
+use alloc::string::String;
+
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+use crate::json::NoStdDeserialize;
+use crate::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Status {
     PENDING,
@@ -9,3 +15,15 @@ pub enum Status {
     IRREVERSIBLE,
     APPROVED,
 }
+
+impl NoStdDeserialize for Status {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        match String::no_std_deserialize(value)?.as_str() {
+            "PENDING" => Ok(Status::PENDING),
+            "PACKED" => Ok(Status::PACKED),
+            "IRREVERSIBLE" => Ok(Status::IRREVERSIBLE),
+            "APPROVED" => Ok(Status::APPROVED),
+            _ => Err(Error::JsonParserError()),
+        }
+    }
+}
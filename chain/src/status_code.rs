@@ -1,8 +1,15 @@
+use alloc::string::String;
+
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::json::NoStdDeserialize;
+use crate::Error;
+
+/// Execution status of a transaction. The discriminants match the numeric codes used by go-iost.
 #[allow(non_camel_case_types)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum StatusCode {
     SUCCESS,
@@ -15,3 +22,40 @@ pub enum StatusCode {
     DUPLICATE_SET_CODE,
     UNKNOWN_ERROR,
 }
+
+impl StatusCode {
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    pub fn from_code(code: i32) -> StatusCode {
+        match code {
+            0 => StatusCode::SUCCESS,
+            1 => StatusCode::GAS_RUN_OUT,
+            2 => StatusCode::BALANCE_NOT_ENOUGH,
+            3 => StatusCode::WRONG_PARAMETER,
+            4 => StatusCode::RUNTIME_ERROR,
+            5 => StatusCode::TIMEOUT,
+            6 => StatusCode::WRONG_TX_FORMAT,
+            7 => StatusCode::DUPLICATE_SET_CODE,
+            _ => StatusCode::UNKNOWN_ERROR,
+        }
+    }
+}
+
+impl NoStdDeserialize for StatusCode {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        match String::no_std_deserialize(value)?.as_str() {
+            "SUCCESS" => Ok(StatusCode::SUCCESS),
+            "GAS_RUN_OUT" => Ok(StatusCode::GAS_RUN_OUT),
+            "BALANCE_NOT_ENOUGH" => Ok(StatusCode::BALANCE_NOT_ENOUGH),
+            "WRONG_PARAMETER" => Ok(StatusCode::WRONG_PARAMETER),
+            "RUNTIME_ERROR" => Ok(StatusCode::RUNTIME_ERROR),
+            "TIMEOUT" => Ok(StatusCode::TIMEOUT),
+            "WRONG_TX_FORMAT" => Ok(StatusCode::WRONG_TX_FORMAT),
+            "DUPLICATE_SET_CODE" => Ok(StatusCode::DUPLICATE_SET_CODE),
+            "UNKNOWN_ERROR" => Ok(StatusCode::UNKNOWN_ERROR),
+            _ => Err(Error::JsonParserError()),
+        }
+    }
+}
@@ -0,0 +1,462 @@
+//! Streaming subscription to contract events and receipts, see [`IostClient::subscribe`].
+
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use futures_core::Stream;
+use futures_util::stream::{self, StreamExt};
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+
+use crate::client::{error_response, IostClient, RequestBody, ResponseBody};
+#[cfg(feature = "std")]
+use crate::de::de_number_or_string_to_i64;
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::node_error::is_transient;
+use crate::telemetry::RequestTelemetry;
+use crate::transport::{ByteStream, HttpTransport};
+use crate::{Error, ErrorMessage, NodeErrorKind, Result};
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum EventTopic {
+    /// events a contract emits with `blockchain.event`
+    CONTRACT_EVENT,
+    /// receipts a contract writes with `blockchain.receipt`, e.g. `token.iost` transfers
+    CONTRACT_RECEIPT,
+}
+
+impl EventTopic {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventTopic::CONTRACT_EVENT => "CONTRACT_EVENT",
+            EventTopic::CONTRACT_RECEIPT => "CONTRACT_RECEIPT",
+        }
+    }
+}
+
+impl NoStdDeserialize for EventTopic {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        match String::no_std_deserialize(value)?.as_str() {
+            "CONTRACT_EVENT" => Ok(EventTopic::CONTRACT_EVENT),
+            "CONTRACT_RECEIPT" => Ok(EventTopic::CONTRACT_RECEIPT),
+            _ => Err(Error::JsonParserError()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct EventFilter {
+    /// only deliver events of this contract, e.g. `token.iost`
+    pub contract_id: String,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct SubscribeRequest {
+    pub topics: Vec<EventTopic>,
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+    pub filter: Option<EventFilter>,
+}
+
+impl SubscribeRequest {
+    /// all events of the given topics emitted by one contract
+    pub fn contract(contract_id: &str, topics: Vec<EventTopic>) -> Self {
+        SubscribeRequest {
+            topics,
+            filter: Some(EventFilter {
+                contract_id: contract_id.to_string(),
+            }),
+        }
+    }
+
+    pub fn no_std_serialize(&self) -> JsonValue {
+        let mut fields = vec![(
+            "topics".chars().collect::<Vec<_>>(),
+            JsonValue::Array(
+                self.topics
+                    .iter()
+                    .map(|topic| JsonValue::String(topic.as_str().chars().collect()))
+                    .collect(),
+            ),
+        )];
+        if let Some(filter) = &self.filter {
+            fields.push((
+                "filter".chars().collect::<Vec<_>>(),
+                JsonValue::Object(vec![(
+                    "contract_id".chars().collect::<Vec<_>>(),
+                    JsonValue::String(filter.contract_id.chars().collect()),
+                )]),
+            ));
+        }
+        JsonValue::Object(fields)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Event {
+    pub topic: EventTopic,
+    /// the JSON encoded content of the event or receipt
+    pub data: String,
+    /// time the event was emitted, in nanoseconds
+    #[cfg_attr(
+        feature = "std",
+        serde(deserialize_with = "de_number_or_string_to_i64")
+    )]
+    pub time: i64,
+}
+
+impl NoStdDeserialize for Event {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(Event {
+            topic: object.field("topic")?,
+            data: object.field("data")?,
+            time: object.field("time")?,
+        })
+    }
+}
+
+/// One line of the stream: `{"result": {"event": ...}}` or `{"error": ...}`.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "std", derive(Deserialize))]
+struct StreamMessage {
+    #[cfg_attr(feature = "std", serde(default))]
+    result: Option<StreamResult>,
+    #[cfg_attr(feature = "std", serde(default))]
+    error: Option<StreamError>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Deserialize))]
+struct StreamResult {
+    event: Event,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Deserialize))]
+struct StreamError {
+    #[cfg_attr(feature = "std", serde(default))]
+    grpc_code: i32,
+    #[cfg_attr(feature = "std", serde(default))]
+    http_code: i32,
+    #[cfg_attr(feature = "std", serde(default))]
+    message: String,
+}
+
+impl NoStdDeserialize for StreamMessage {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(StreamMessage {
+            result: object.field_or_default("result")?,
+            error: object.field_or_default("error")?,
+        })
+    }
+}
+
+impl NoStdDeserialize for StreamResult {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(StreamResult {
+            event: object.field("event")?,
+        })
+    }
+}
+
+impl NoStdDeserialize for StreamError {
+    fn no_std_deserialize(value: &JsonValue) -> Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(StreamError {
+            grpc_code: object.field_or_default("grpc_code")?,
+            http_code: object.field_or_default("http_code")?,
+            message: object.field_or_default("message")?,
+        })
+    }
+}
+
+/// Decode one line of the stream; `None` for lines without an event.
+fn decode_line(line: &[u8]) -> Result<Option<Event>> {
+    let message = StreamMessage::from_body(line)?;
+    if let Some(error) = message.error {
+        let message = ErrorMessage {
+            code: error.grpc_code,
+            message: error.message,
+        };
+        let kind = NodeErrorKind::classify(error.http_code as u16, &message);
+        return Err(Error::NodeError(kind, message));
+    }
+    Ok(message.result.map(|result| result.event))
+}
+
+/// State of a subscription across reconnects.
+struct Subscription<'a, T> {
+    client: &'a IostClient<T>,
+    url: String,
+    request: SubscribeRequest,
+    connection: Option<ByteStream>,
+    /// whether the current connection delivered anything
+    received: bool,
+    buffer: Vec<u8>,
+    events: VecDeque<Event>,
+    /// events at or before this time are skipped
+    resume_after: Option<i64>,
+    /// time of the last delivered event, and the data of every event delivered at that time
+    last_time: i64,
+    last_data: Vec<String>,
+    failures: u32,
+    done: bool,
+}
+
+impl<'a, T: HttpTransport> Subscription<'a, T> {
+    async fn next_event(&mut self) -> Option<Result<Event>> {
+        while !self.done {
+            if let Some(event) = self.events.pop_front() {
+                if self.is_new(&event) {
+                    self.failures = 0;
+                    return Some(Ok(event));
+                }
+                continue;
+            }
+
+            let result = match self.connection.as_mut() {
+                None => self.connect().await,
+                Some(connection) => match connection.next().await {
+                    Some(Ok(chunk)) => {
+                        self.received = true;
+                        self.read_chunk(&chunk)
+                    }
+                    Some(Err(err)) => Err(err),
+                    // a clean close after data is routine (proxy idle timeouts); reconnect
+                    None if self.received => {
+                        self.disconnect();
+                        Ok(())
+                    }
+                    None => Err(Error::HttpTransportError(
+                        "subscription closed by the node".to_string(),
+                    )),
+                },
+            };
+            if let Err(err) = result {
+                self.disconnect();
+                if let Some(err) = self.fail(err).await {
+                    return Some(Err(err));
+                }
+            }
+        }
+        None
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        let telemetry = RequestTelemetry::start(self.client.host(), "subscribe");
+        let result = self.open(&telemetry).instrument(telemetry.span()).await;
+        telemetry.finish(&result);
+        result
+    }
+
+    async fn open(&mut self, telemetry: &RequestTelemetry) -> Result<()> {
+        let body = self.request.to_body()?;
+        let response = self.client.transport().post_stream(&self.url, body).await?;
+        telemetry.status(response.status);
+        if response.status != 200 {
+            let mut body = Vec::new();
+            let mut chunks = response.body;
+            while let Some(Ok(chunk)) = chunks.next().await {
+                body.extend_from_slice(&chunk);
+            }
+            return Err(error_response(response.status, &body));
+        }
+        self.connection = Some(response.body);
+        self.received = false;
+        Ok(())
+    }
+
+    fn disconnect(&mut self) {
+        self.connection = None;
+        self.buffer.clear();
+    }
+
+    /// Split a chunk into lines and decode the complete ones.
+    fn read_chunk(&mut self, chunk: &[u8]) -> Result<()> {
+        self.buffer.extend_from_slice(chunk);
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            if let Some(event) = decode_line(&line)? {
+                self.events.push_back(event);
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip events from before `resume_after`, and events the node delivers again after a
+    /// reconnect.
+    fn is_new(&mut self, event: &Event) -> bool {
+        if matches!(self.resume_after, Some(after) if event.time <= after) {
+            return false;
+        }
+        if event.time < self.last_time
+            || (event.time == self.last_time && self.last_data.contains(&event.data))
+        {
+            return false;
+        }
+        if event.time > self.last_time {
+            self.last_time = event.time;
+            self.last_data.clear();
+        }
+        self.last_data.push(event.data.clone());
+        true
+    }
+
+    /// Wait before reconnecting, or give up and return the error.
+    async fn fail(&mut self, err: Error) -> Option<Error> {
+        let policy = self.client.retry_policy();
+        if !is_transient(&err) || self.failures >= policy.max_retries {
+            self.done = true;
+            return Some(err);
+        }
+        let backoff = policy.backoff(self.failures);
+        self.failures += 1;
+        self.client.transport().sleep(backoff).await;
+        None
+    }
+}
+
+pub(crate) fn subscribe<'a, T: HttpTransport>(
+    client: &'a IostClient<T>,
+    request: &SubscribeRequest,
+    resume_after: Option<i64>,
+) -> impl Stream<Item = Result<Event>> + 'a {
+    let subscription = Subscription {
+        client,
+        url: alloc::format!("{}/subscribe", client.host()),
+        request: request.clone(),
+        connection: None,
+        received: false,
+        buffer: Vec::new(),
+        events: VecDeque::new(),
+        resume_after,
+        last_time: i64::MIN,
+        last_data: Vec::new(),
+        failures: 0,
+        done: false,
+    };
+    stream::unfold(subscription, |mut subscription| async move {
+        let event = subscription.next_event().await?;
+        Some((event, subscription))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::transport::block_on;
+    use crate::RetryPolicy;
+    use core::time::Duration;
+
+    fn collect(
+        client: &IostClient<MockTransport>,
+        resume_after: Option<i64>,
+    ) -> Vec<Result<Event>> {
+        let request = SubscribeRequest::contract("token.iost", vec![EventTopic::CONTRACT_RECEIPT]);
+        block_on(subscribe(client, &request, resume_after).collect::<Vec<_>>())
+    }
+
+    /// Client of a node serving one streamed response per connection, in order.
+    fn client(connections: Vec<(u16, Vec<&'static str>)>) -> IostClient<MockTransport> {
+        let transport = MockTransport::new();
+        transport.set_streams(connections);
+        IostClient::with_transport("http://127.0.0.1:30001", transport)
+    }
+
+    const EVENT_1: &str = r#"{"result":{"event":{"topic":"CONTRACT_RECEIPT","data":"[\"iost\",\"a\",\"b\",\"1\",\"\"]","time":"1000"}}}"#;
+    const EVENT_2: &str = r#"{"result":{"event":{"topic":"CONTRACT_RECEIPT","data":"[\"iost\",\"a\",\"b\",\"2\",\"\"]","time":"2000"}}}"#;
+
+    #[test]
+    fn should_parse_chunked_events() {
+        let client = client(vec![(
+            200,
+            vec![&EVENT_1[..20], &EVENT_1[20..], "\n\n", EVENT_2, "\n"],
+        )]);
+        let events = collect(&client, None);
+
+        // two events, then the error for the refused reconnects
+        assert_eq!(events.len(), 3);
+        let event = events[0].as_ref().unwrap();
+        assert_eq!(event.topic, EventTopic::CONTRACT_RECEIPT);
+        assert_eq!(event.time, 1000);
+        assert_eq!(events[1].as_ref().unwrap().time, 2000);
+        assert!(events[2].is_err());
+
+        let request = &client.transport().requests()[0];
+        assert_eq!(request.url, "http://127.0.0.1:30001/subscribe");
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"topics": ["CONTRACT_RECEIPT"], "filter": {"contract_id": "token.iost"}})
+        );
+    }
+
+    #[test]
+    fn should_reconnect_and_skip_delivered_events() {
+        let client = client(vec![
+            (200, vec![EVENT_1, "\n"]),
+            (503, vec!["<html>Service Unavailable</html>"]),
+            (200, vec![EVENT_1, "\n", EVENT_2, "\n"]),
+        ])
+        .with_retry_policy(RetryPolicy {
+            max_retries: 1,
+            ..RetryPolicy::default()
+        });
+        let events = collect(&client, None);
+
+        let times: Vec<i64> = events
+            .iter()
+            .filter_map(|event| event.as_ref().ok())
+            .map(|event| event.time)
+            .collect();
+        assert_eq!(times, vec![1000, 2000]);
+        assert_eq!(client.transport().count("subscribe"), 5);
+        assert_eq!(
+            client.transport().sleeps(),
+            vec![Duration::from_millis(200), Duration::from_millis(200)]
+        );
+    }
+
+    #[test]
+    fn should_resume_after_time() {
+        let client = client(vec![(200, vec![EVENT_1, "\n", EVENT_2, "\n"])])
+            .with_retry_policy(RetryPolicy::none());
+        let events = collect(&client, Some(1000));
+        assert_eq!(events[0].as_ref().unwrap().time, 2000);
+    }
+
+    #[test]
+    fn should_stop_on_permanent_error() {
+        let client = client(vec![(
+            200,
+            vec![
+                r#"{"error":{"grpc_code":3,"http_code":400,"message":"invalid topic"}}"#,
+                "\n",
+            ],
+        )]);
+        let events = collect(&client, None);
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            Err(Error::NodeError(NodeErrorKind::InvalidRequest, msg)) => {
+                assert_eq!(msg.message, "invalid topic")
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
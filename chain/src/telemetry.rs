@@ -0,0 +1,211 @@
+//! Tracing and metrics for the requests of [`IostClient`](crate::IostClient).
+//!
+//! Every request runs in an `iost_request` span at debug level, retries included, with the
+//! fields `endpoint`, `host`, `status` (HTTP status of the last attempt), `latency_ms` and,
+//! for requests about a transaction, `tx_hash`. A finished request emits an event: debug when
+//! it succeeded, warn when it failed. `send_tx` and `exec_tx` wrap their request in an
+//! `iost_send_tx` or `iost_exec_tx` span recording the hash the node returned.
+//!
+//! With the `metrics` feature, requests are counted in `iost_client_requests_total` by
+//! `endpoint` and `outcome`, and their latency is recorded in the
+//! `iost_client_request_duration_seconds` histogram by `endpoint`.
+
+use tracing::field::Empty;
+use tracing::Span;
+
+use crate::{Error, Result};
+
+pub(crate) struct RequestTelemetry {
+    span: Span,
+    #[cfg(feature = "metrics")]
+    endpoint: alloc::string::String,
+    #[cfg(feature = "std")]
+    started: std::time::Instant,
+}
+
+impl RequestTelemetry {
+    /// Start timing a request of `path`, relative to the API root of `host`.
+    pub(crate) fn start(host: &str, path: &str) -> Self {
+        let mut segments = path.split('/');
+        let endpoint = segments.next().unwrap_or_default();
+        let span = tracing::debug_span!(
+            "iost_request",
+            endpoint,
+            host,
+            status = Empty,
+            latency_ms = Empty,
+            tx_hash = Empty,
+        );
+        if endpoint == "getTxByHash" || endpoint == "getTxReceiptByTxHash" {
+            if let Some(hash) = segments.next() {
+                span.record("tx_hash", hash);
+            }
+        }
+        RequestTelemetry {
+            span,
+            #[cfg(feature = "metrics")]
+            endpoint: endpoint.into(),
+            #[cfg(feature = "std")]
+            started: std::time::Instant::now(),
+        }
+    }
+
+    /// The span to run the request in.
+    pub(crate) fn span(&self) -> Span {
+        self.span.clone()
+    }
+
+    /// Record the HTTP status of an attempt.
+    pub(crate) fn status(&self, status: u16) {
+        self.span.record("status", status);
+    }
+
+    /// Record the outcome of the request, after the last attempt.
+    pub(crate) fn finish<R>(&self, result: &Result<R>) {
+        let _enter = self.span.enter();
+        #[cfg(feature = "std")]
+        {
+            let latency = self.started.elapsed();
+            self.span.record("latency_ms", latency.as_millis() as u64);
+            #[cfg(feature = "metrics")]
+            metrics::histogram!(
+                "iost_client_request_duration_seconds",
+                latency.as_secs_f64(),
+                "endpoint" => self.endpoint.clone()
+            );
+        }
+        let outcome = outcome(result);
+        match result {
+            Ok(_) => tracing::debug!(outcome, "request succeeded"),
+            Err(err) => tracing::warn!(outcome, error = %err, "request failed"),
+        }
+        #[cfg(feature = "metrics")]
+        metrics::counter!(
+            "iost_client_requests_total",
+            1,
+            "endpoint" => self.endpoint.clone(),
+            "outcome" => outcome
+        );
+    }
+}
+
+/// Label of a request's result: `ok`, `node_error`, `decode_error` or `transport_error`.
+fn outcome<R>(result: &Result<R>) -> &'static str {
+    match result {
+        Ok(_) => "ok",
+        Err(Error::NodeError(..)) | Err(Error::ErrorMessage(_)) => "node_error",
+        Err(Error::JsonParserError()) => "decode_error",
+        Err(_) => "transport_error",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mock::{MockTransport, Reply};
+    use crate::transport::block_on;
+    use crate::{IostClient, RetryPolicy, Tx};
+    use alloc::boxed::Box;
+    use alloc::format;
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::fmt;
+    use std::sync::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    type FieldValues = Vec<(String, String)>;
+
+    /// Fields of the spans by span name, and of the events.
+    #[derive(Default)]
+    struct Recorder {
+        spans: Mutex<Vec<(String, FieldValues)>>,
+        events: Mutex<Vec<FieldValues>>,
+    }
+
+    struct Fields<'a>(&'a mut FieldValues);
+
+    impl<'a> Visit for Fields<'a> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push((field.name().to_string(), value.to_string()));
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{:?}", value)));
+        }
+    }
+
+    impl Recorder {
+        fn field(&self, span: &str, field: &str) -> Option<String> {
+            let spans = self.spans.lock().unwrap();
+            let (_, fields) = spans.iter().find(|(name, _)| name == span)?;
+            fields
+                .iter()
+                .find(|(name, _)| name == field)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    impl Subscriber for &'static Recorder {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes) -> Id {
+            let mut fields = Vec::new();
+            span.record(&mut Fields(&mut fields));
+            let mut spans = self.spans.lock().unwrap();
+            spans.push((span.metadata().name().to_string(), fields));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record) {
+            let mut spans = self.spans.lock().unwrap();
+            let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+            values.record(&mut Fields(fields));
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event) {
+            let mut fields = Vec::new();
+            event.record(&mut Fields(&mut fields));
+            self.events.lock().unwrap().push(fields);
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[test]
+    fn should_trace_requests() {
+        let recorder: &'static Recorder = Box::leak(Box::new(Recorder::default()));
+        let transport = MockTransport::new();
+        transport.set_response("getTxByHash", Reply::error(404, 5, "tx not found"));
+        let client = IostClient::with_transport("http://127.0.0.1:30001", transport)
+            .with_retry_policy(RetryPolicy::none());
+        let tx = Tx::from_action(vec![]);
+        tracing::subscriber::with_default(recorder, || {
+            assert!(block_on(client.get_tx_by_hash("abc")).is_err());
+            block_on(client.send_tx(&tx)).unwrap();
+        });
+
+        let field = |field| recorder.field("iost_request", field);
+        assert_eq!(field("endpoint").unwrap(), "getTxByHash");
+        assert_eq!(field("host").unwrap(), "http://127.0.0.1:30001");
+        assert_eq!(field("tx_hash").unwrap(), "abc");
+        assert_eq!(field("status").unwrap(), "404");
+        assert!(field("latency_ms").is_some());
+        let events = recorder.events.lock().unwrap();
+        assert!(events[0].contains(&("outcome".to_string(), "node_error".to_string())));
+        assert!(events[1].contains(&("outcome".to_string(), "ok".to_string())));
+
+        assert_eq!(
+            recorder.field("iost_send_tx", "tx_hash").unwrap(),
+            tx.hash().unwrap().to_string()
+        );
+    }
+}
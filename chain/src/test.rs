@@ -76,6 +76,7 @@ fn should_tx_serialization_and_sign_successful() {
         publisher_sigs: vec![],
         signers: vec![],
         signatures: vec![],
+        referred_tx: vec![],
     };
     // dbg!(tx.num_bytes());
     let result = tx.to_serialize_data();
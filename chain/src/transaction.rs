@@ -1,25 +1,49 @@
 use alloc::string::String;
 use alloc::vec::Vec;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use crate::{AmountLimit, IostAction, Signature};
+#[cfg(feature = "std")]
+use crate::de::{de_number_or_string_to_f64, de_number_or_string_to_i64};
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::{AmountLimit, IostAction, Signature, TxReceipt};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Transaction {
     /// transaction's hash
     pub hash: String,
     /// timestamp of the transaction
+    #[cfg_attr(
+        feature = "std",
+        serde(deserialize_with = "de_number_or_string_to_i64")
+    )]
     pub time: i64,
     /// the expiration of the transaction
+    #[cfg_attr(
+        feature = "std",
+        serde(deserialize_with = "de_number_or_string_to_i64")
+    )]
     pub expiration: i64,
     /// GAS ratio, we recommend it to be 1.00 (1.00 – 100.00). Raise the ratio to let the network pack it faster
+    #[cfg_attr(
+        feature = "std",
+        serde(deserialize_with = "de_number_or_string_to_f64")
+    )]
     pub gas_ratio: f64,
     /// Upper limits of GAS. This transaction will never cost more GAS than this amount
+    #[cfg_attr(
+        feature = "std",
+        serde(deserialize_with = "de_number_or_string_to_f64")
+    )]
     pub gas_limit: f64,
     /// Transactions will be delayed by this much, in nanosecond
+    #[cfg_attr(
+        feature = "std",
+        serde(deserialize_with = "de_number_or_string_to_i64")
+    )]
     pub delay: i64,
     /// id of blockchain on which the transaction could be executed
     pub chain_id: i32,
@@ -30,11 +54,36 @@ pub struct Transaction {
     /// sender of the transaction, who is responsible for fees
     pub publisher: String,
     /// dependency of transaction generation; used for delayed transactions
+    #[cfg_attr(feature = "std", serde(default))]
     pub referred_tx: String,
     /// Users may specify token limits. For example, {"iost": 100} specifies each signers will not spend more than 100 IOST for the transaction
     pub amount_limit: Vec<AmountLimit>,
     /// The signatures of signers
+    #[cfg_attr(feature = "std", serde(default))]
     pub signatures: Vec<Signature>,
-    // the receipt of the transaction Action
-    // pub tx_receipt: TxReceipt,
+    /// the receipt of the transaction Action, only present on complete blocks and tx queries
+    #[cfg_attr(feature = "std", serde(default))]
+    pub tx_receipt: Option<TxReceipt>,
+}
+
+impl NoStdDeserialize for Transaction {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(Transaction {
+            hash: object.field("hash")?,
+            time: object.field("time")?,
+            expiration: object.field("expiration")?,
+            gas_ratio: object.field("gas_ratio")?,
+            gas_limit: object.field("gas_limit")?,
+            delay: object.field("delay")?,
+            chain_id: object.field("chain_id")?,
+            actions: object.field("actions")?,
+            signers: object.field("signers")?,
+            publisher: object.field("publisher")?,
+            referred_tx: object.field_or_default("referred_tx")?,
+            amount_limit: object.field("amount_limit")?,
+            signatures: object.field_or_default("signatures")?,
+            tx_receipt: object.field_or_default("tx_receipt")?,
+        })
+    }
 }
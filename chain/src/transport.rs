@@ -0,0 +1,170 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::future::Future;
+use core::pin::Pin;
+#[cfg(all(feature = "std", any(test, feature = "blocking")))]
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+
+use async_trait::async_trait;
+use futures_core::Stream;
+#[cfg(feature = "client")]
+use futures_util::StreamExt;
+
+use crate::{Error, Result};
+
+/// status code and raw body of an HTTP response
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+/// chunks of a response body, as they arrive
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Vec<u8>>> + Send>>;
+
+/// status code and streamed body of an HTTP response
+pub struct HttpStreamResponse {
+    pub status: u16,
+    pub body: ByteStream,
+}
+
+/// Sends the HTTP requests of an `IostClient`. Implement it to run the client on something
+/// other than reqwest, e.g. `sp_runtime::offchain::http` inside an offchain worker.
+#[async_trait]
+pub trait HttpTransport: Send + Sync {
+    async fn get(&self, url: &str) -> Result<HttpResponse>;
+
+    /// POST a JSON encoded body
+    async fn post(&self, url: &str, body: Vec<u8>) -> Result<HttpResponse>;
+
+    /// Wait before retrying a request.
+    async fn sleep(&self, duration: Duration);
+
+    /// POST a JSON encoded body and read the response as it arrives, for long-lived
+    /// responses like `subscribe`. Transports that can't stream keep the default, which fails.
+    async fn post_stream(&self, _url: &str, _body: Vec<u8>) -> Result<HttpStreamResponse> {
+        Err(Error::StreamingUnsupported())
+    }
+}
+
+/// Default transport, backed by `reqwest::Client`.
+#[cfg(feature = "client")]
+#[derive(Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "client")]
+impl ReqwestTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_client(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+
+    pub fn with_config(config: &crate::ClientConfig) -> Result<Self> {
+        Ok(Self::with_client(config.build()?))
+    }
+}
+
+#[cfg(feature = "client")]
+#[async_trait]
+impl HttpTransport for ReqwestTransport {
+    async fn get(&self, url: &str) -> Result<HttpResponse> {
+        let response = self.client.get(url).send().await.map_err(Error::Reqwest)?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await.map_err(Error::Reqwest)?;
+        Ok(HttpResponse {
+            status,
+            body: body.to_vec(),
+        })
+    }
+
+    async fn post(&self, url: &str, body: Vec<u8>) -> Result<HttpResponse> {
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(Error::Reqwest)?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await.map_err(Error::Reqwest)?;
+        Ok(HttpResponse {
+            status,
+            body: body.to_vec(),
+        })
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::delay_for(duration).await
+    }
+
+    async fn post_stream(&self, url: &str, body: Vec<u8>) -> Result<HttpStreamResponse> {
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(Error::Reqwest)?;
+        let status = response.status().as_u16();
+        let body = response
+            .bytes_stream()
+            .map(|chunk| chunk.map(|bytes| bytes.to_vec()).map_err(Error::Reqwest));
+        Ok(HttpStreamResponse {
+            status,
+            body: Box::pin(body),
+        })
+    }
+}
+
+/// Run `future`, giving up with `None` once `transport` has slept for `duration`.
+#[cfg(feature = "std")]
+pub(crate) async fn timeout<T, F>(transport: &T, duration: Duration, future: F) -> Option<F::Output>
+where
+    T: HttpTransport + ?Sized,
+    F: Future,
+{
+    use futures_util::future::{select, Either};
+
+    match select(Box::pin(future), transport.sleep(duration)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+/// Run a future to completion on the current thread, without an executor.
+///
+/// Meant for transports that finish their work synchronously, like the blocking one. The
+/// thread is parked while the future is pending, and woken by the future's waker.
+#[cfg(all(feature = "std", any(test, feature = "blocking")))]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::thread::{self, Thread};
+
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark()
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
@@ -8,9 +9,15 @@ use lite_json::{JsonValue, NumberValue, Serialize};
 use serde::{Deserialize, Serialize as SerSerialize};
 use sha3::{Digest, Sha3_256};
 
-use crate::Error::{InvalidPublisherSignature, InvalidSignature};
+use keys::algorithm;
+
+use crate::Error::{
+    DuplicateSignerKey, InvalidPublisherSignature, InvalidSignature, InvalidTx,
+    MissingSignerSignature, UnsupportedAlgorithm,
+};
 use crate::{
-    AmountLimit, IostAction, NumberBytes, Read, SerializeData, Signature, Write, WriteError,
+    AmountLimit, IostAction, KeySigner, NumberBytes, Read, SerializeData, Signature, TxHash,
+    TxSigner, Write, WriteError,
 };
 
 #[derive(Clone, Default, Debug, Read, Write, NumberBytes, SerializeData)]
@@ -41,6 +48,12 @@ pub struct Tx {
     pub signers: Vec<String>,
     /// Signature of signers. Each signer can have one or more signatures, so the length is not less than the length of signers
     pub signatures: Vec<Signature>,
+    /// Hash of the delayed transaction a deferred transaction was created from, see
+    /// [`Tx::deferred`]. Empty for transactions that are published; it isn't sent to the node,
+    /// and isn't part of the derived `Read`/`Write` layout.
+    #[cfg_attr(feature = "std", serde(skip))]
+    #[iost_skip]
+    pub referred_tx: Vec<u8>,
 }
 
 fn expand<T>(x: &Vec<T>, bytes: &mut [u8], pos: &mut usize) -> Result<(), WriteError>
@@ -54,6 +67,22 @@ where
     Ok(())
 }
 
+/// `gas` as a JSON number, with the two decimals the signed bytes carry.
+fn gas_number(gas: f64) -> JsonValue {
+    let hundredths = (gas * 100.0) as i64;
+    let (fraction, fraction_length) = match hundredths % 100 {
+        0 => (0, 0),
+        cents if cents % 10 == 0 => (cents / 10, 1),
+        cents => (cents, 2),
+    };
+    JsonValue::Number(NumberValue {
+        integer: hundredths / 100,
+        fraction: fraction as u64,
+        fraction_length,
+        exponent: 0,
+    })
+}
+
 impl Tx {
     pub fn new(time: i64, expiration: i64, chain_id: u32, actions: Vec<IostAction>) -> Self {
         let amount_limit = AmountLimit {
@@ -74,6 +103,7 @@ impl Tx {
             publisher_sigs: vec![],
             signers: vec![],
             signatures: vec![],
+            referred_tx: vec![],
         }
     }
 
@@ -98,6 +128,7 @@ impl Tx {
             publisher_sigs: vec![],
             signers: vec![],
             signatures: vec![],
+            referred_tx: vec![],
         }
     }
 
@@ -123,21 +154,11 @@ impl Tx {
             ),
             (
                 "gas_ratio".chars().collect::<Vec<_>>(),
-                JsonValue::Number(NumberValue {
-                    integer: self.gas_ratio as i64,
-                    fraction: 0,
-                    fraction_length: 0,
-                    exponent: 0,
-                }),
+                gas_number(self.gas_ratio),
             ),
             (
                 "gas_limit".chars().collect::<Vec<_>>(),
-                JsonValue::Number(NumberValue {
-                    integer: self.gas_limit as i64,
-                    fraction: 0,
-                    fraction_length: 0,
-                    exponent: 0,
-                }),
+                gas_number(self.gas_limit),
             ),
             (
                 "delay".chars().collect::<Vec<_>>(),
@@ -210,22 +231,27 @@ impl Tx {
     }
 
     #[inline]
-    fn number_bytes(&self, with_sign: bool) -> usize {
+    fn number_bytes(&self, signatures: Option<&Vec<Vec<u8>>>) -> usize {
         let res = 48
             + self.signers.num_bytes()
-            + self.signers.len() * 4
             + self.actions.num_bytes()
             + self.actions.len() * 4
             + self.amount_limit.num_bytes()
             + self.amount_limit.len() * 4;
-        if with_sign {
-            res + self.signatures.num_bytes() + self.signatures.len() * 4
-        } else {
-            res
+        match signatures {
+            Some(signatures) => res + signatures.num_bytes(),
+            None => res,
         }
     }
 
-    fn write(&self, bytes: &mut [u8], pos: &mut usize, with_sign: bool) -> Result<(), WriteError> {
+    /// `signatures` are the signatures of the signers in go-iost encoding, written when the
+    /// publisher signs.
+    fn write(
+        &self,
+        bytes: &mut [u8],
+        pos: &mut usize,
+        signatures: Option<&Vec<Vec<u8>>>,
+    ) -> Result<(), WriteError> {
         self.time.clone().write(bytes, pos);
         self.expiration.clone().write(bytes, pos);
         let mut ratio = (self.gas_ratio * 100.0) as i64;
@@ -238,63 +264,207 @@ impl Tx {
         // reserved field
         0_i32.write(bytes, pos);
 
-        self.signers.len().write(bytes, pos)?;
-        expand::<String>(&self.signers, bytes, pos);
+        self.signers.write(bytes, pos)?;
         self.actions.len().write(bytes, pos);
         expand::<IostAction>(&self.actions, bytes, pos);
         self.amount_limit.len().write(bytes, pos);
         expand::<AmountLimit>(&self.amount_limit, bytes, pos);
-        if with_sign {
-            self.signatures.len().write(bytes, pos);
-            expand::<Signature>(&self.signatures, bytes, pos);
+        if let Some(signatures) = signatures {
+            signatures.write(bytes, pos)?;
         }
         Ok(())
     }
 
+    /// Bytes to hash for a signature: without `with_sign` for the signers, with it for the
+    /// publisher, which also covers the signatures of the signers.
     pub fn customized_to_serialize_data(&self, with_sign: bool) -> crate::Result<Vec<u8>> {
-        let mut data = vec![0u8; self.number_bytes(with_sign)];
-        self.write(&mut data, &mut 0, with_sign)
-            .map_err(crate::Error::BytesWriteError)
-            .unwrap();
-        Ok(data.to_vec())
+        let signatures = if with_sign {
+            Some(
+                self.signatures
+                    .iter()
+                    .map(Signature::to_bytes)
+                    .collect::<crate::Result<Vec<_>>>()?,
+            )
+        } else {
+            None
+        };
+        let mut data = vec![0u8; self.number_bytes(signatures.as_ref())];
+        self.write(&mut data, &mut 0, signatures.as_ref())
+            .map_err(crate::Error::BytesWriteError)?;
+        Ok(data)
+    }
+
+    /// SHA3-256 of `customized_to_serialize_data(with_sign)`, the message that is signed.
+    pub(crate) fn digest(&self, with_sign: bool) -> crate::Result<Vec<u8>> {
+        let mut hasher = Sha3_256::new();
+        hasher.input(self.customized_to_serialize_data(with_sign)?);
+        Ok(hasher.result().to_vec())
     }
 
+    /// Hash of the transaction on chain: SHA3-256 over the serialized form with the
+    /// signatures of the signers, then the referred transaction, the signatures of the
+    /// publisher and the publisher's name.
+    pub fn hash(&self) -> crate::Result<TxHash> {
+        let publisher_sigs = self
+            .publisher_sigs
+            .iter()
+            .map(Signature::to_bytes)
+            .collect::<crate::Result<Vec<_>>>()?;
+        let mut data = self.customized_to_serialize_data(true)?;
+        let mut pos = data.len();
+        data.resize(
+            pos + self.referred_tx.num_bytes()
+                + publisher_sigs.num_bytes()
+                + self.publisher.num_bytes(),
+            0,
+        );
+        self.referred_tx
+            .write(&mut data, &mut pos)
+            .and_then(|_| publisher_sigs.write(&mut data, &mut pos))
+            .and_then(|_| self.publisher.write(&mut data, &mut pos))
+            .map_err(crate::Error::BytesWriteError)?;
+        Ok(TxHash::digest(&data))
+    }
+
+    /// Declare `signer`, as `account@permission` (e.g. `bob@active`), as a signer besides the
+    /// publisher. Signers must be declared before anyone signs, since they are part of every
+    /// signed message.
+    pub fn add_signer(&mut self, signer: &str) -> crate::Result<()> {
+        if self.signers.iter().any(|declared| declared == signer) {
+            return Ok(());
+        }
+        if !is_account_permission(signer) {
+            return Err(InvalidTx(format!(
+                "signer {} is not account@permission",
+                signer
+            )));
+        }
+        if !self.signatures.is_empty() || !self.publisher_sigs.is_empty() {
+            return Err(InvalidTx(format!(
+                "signer {} added after the transaction was signed",
+                signer
+            )));
+        }
+        self.signers.push(signer.to_string());
+        Ok(())
+    }
+
+    /// Add the signature of the declared `signer` with `sec_key`. Signers sign before the
+    /// publisher, whose signature covers theirs.
+    pub fn sign_as_signer(
+        &mut self,
+        signer: &str,
+        sign_algorithm: &str,
+        sec_key: &[u8],
+    ) -> crate::Result<()> {
+        let key = KeySigner::new(sign_algorithm, sec_key)?;
+        let digest = self.signer_digest(signer)?;
+        let signature = checked(&digest, &key, key.sign_digest(&digest)?)?;
+        self.add_signer_signature(signature)
+    }
+
+    /// Add a signature of the declared `signer` from `key`.
+    pub async fn sign_as_signer_with(
+        &mut self,
+        signer: &str,
+        key: &dyn TxSigner,
+    ) -> crate::Result<()> {
+        let digest = self.signer_digest(signer)?;
+        let signature = checked(&digest, key, key.sign(&digest).await?)?;
+        self.add_signer_signature(signature)
+    }
+
+    /// The digest the declared `signer` signs, as long as the publisher hasn't signed.
+    fn signer_digest(&self, signer: &str) -> crate::Result<Vec<u8>> {
+        if !self.signers.iter().any(|declared| declared == signer) {
+            return Err(InvalidTx(format!("{} is not a signer", signer)));
+        }
+        if !self.publisher_sigs.is_empty() {
+            return Err(InvalidTx(format!("{} signed after the publisher", signer)));
+        }
+        self.digest(false)
+    }
+
+    fn add_signer_signature(&mut self, signature: Signature) -> crate::Result<()> {
+        if self
+            .signatures
+            .iter()
+            .any(|other| other.public_key == signature.public_key)
+        {
+            return Err(DuplicateSignerKey(signature.public_key));
+        }
+        self.signatures.push(signature);
+        Ok(())
+    }
+
+    /// Sign as publisher `account_name` with `sec_key`, replacing any publisher signatures.
     pub fn sign(
         &mut self,
         account_name: String,
         sign_algorithm: &str,
         sec_key: &[u8],
-    ) -> Result<(), WriteError> {
-        self.publisher = account_name;
-
-        if self.publisher_sigs.len() == 0 {
-            let tx_bytes = self.customized_to_serialize_data(true).unwrap();
-            // create a SHA3-256 object
-            let mut hasher = Sha3_256::new();
-            hasher.input(tx_bytes);
-            let result = hasher.result();
-            self.publisher_sigs =
-                vec![Signature::sign(result.as_ref(), sign_algorithm, sec_key).unwrap()];
-        }
+    ) -> crate::Result<()> {
+        let key = KeySigner::new(sign_algorithm, sec_key)?;
+        self.publisher_sigs.clear();
+        let digest = self.publisher_digest(&account_name)?;
+        let signature = checked(&digest, &key, key.sign_digest(&digest)?)?;
+        self.publisher_sigs.push(signature);
+        Ok(())
+    }
+
+    /// Add a signature of publisher `account_name` from `signer`. A publisher whose permission
+    /// needs several keys signs once with each.
+    pub async fn sign_with(
+        &mut self,
+        account_name: &str,
+        signer: &dyn TxSigner,
+    ) -> crate::Result<()> {
+        let digest = self.publisher_digest(account_name)?;
+        let signature = checked(&digest, signer, signer.sign(&digest).await?)?;
+        self.publisher_sigs.push(signature);
         Ok(())
     }
 
+    /// The digest publisher `account_name` signs, making it the publisher unless another one
+    /// has signed.
+    fn publisher_digest(&mut self, account_name: &str) -> crate::Result<Vec<u8>> {
+        if !self.publisher_sigs.is_empty() && self.publisher != account_name {
+            return Err(InvalidTx(format!(
+                "{} signed as publisher after {}",
+                account_name, self.publisher
+            )));
+        }
+        self.publisher = account_name.to_string();
+        self.digest(true)
+    }
+
+    /// Check that the signatures of the signers and of the publisher are valid, that no key
+    /// signed twice as a signer, and that there are at least as many signer signatures as
+    /// signers. This doesn't tell whether every signer signed: which account a key belongs to
+    /// is on chain, so the node checks that each signer's permission is satisfied.
     pub fn verify(&self) -> crate::Result<()> {
+        if self.signatures.len() < self.signers.len() {
+            return Err(MissingSignerSignature());
+        }
+        for (i, signature) in self.signatures.iter().enumerate() {
+            if self.signatures[..i]
+                .iter()
+                .any(|other| other.public_key == signature.public_key)
+            {
+                return Err(DuplicateSignerKey(signature.public_key.clone()));
+            }
+        }
+        let digest = self.digest(false)?;
         for signature in &self.signatures {
-            let tx_bytes = self.customized_to_serialize_data(false).unwrap();
-            let mut hasher = Sha3_256::new();
-            hasher.input(tx_bytes);
-            let result = hasher.result();
-            if !signature.verify(result.as_slice()) {
+            signature.algorithm_byte()?;
+            if !signature.verify(&digest) {
                 return Err(InvalidSignature());
             }
         }
+        let digest = self.digest(true)?;
         for publisher_sig in &self.publisher_sigs {
-            let tx_bytes = self.customized_to_serialize_data(true).unwrap();
-            let mut hasher = Sha3_256::new();
-            hasher.input(tx_bytes);
-            let result = hasher.result();
-            if !publisher_sig.verify(result.as_slice()) {
+            publisher_sig.algorithm_byte()?;
+            if !publisher_sig.verify(&digest) {
                 return Err(InvalidPublisherSignature());
             }
         }
@@ -302,6 +472,30 @@ impl Tx {
     }
 }
 
+/// Check that `signature` of `digest` is of the algorithm and key of `signer` and verifies, so
+/// a faulty remote signer can't slip in a signature the node rejects.
+fn checked(digest: &[u8], signer: &dyn TxSigner, signature: Signature) -> crate::Result<Signature> {
+    let algorithm = algorithm::normalize(signer.algorithm())
+        .ok_or_else(|| UnsupportedAlgorithm(signer.algorithm().to_string()))?;
+    let public_key = base64::decode(&signature.public_key).map_err(|_| InvalidSignature())?;
+    if algorithm::normalize(&signature.algorithm) != Some(algorithm)
+        || public_key != signer.public_key()
+        || !signature.verify(digest)
+    {
+        return Err(InvalidSignature());
+    }
+    Ok(signature)
+}
+
+/// Whether `signer` looks like `account@permission`.
+pub(crate) fn is_account_permission(signer: &str) -> bool {
+    let mut parts = signer.split('@');
+    matches!(
+        (parts.next(), parts.next(), parts.next()),
+        (Some(account), Some(permission), None) if !account.is_empty() && !permission.is_empty()
+    )
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -342,7 +536,8 @@ mod test {
             "lispczz4".to_string(),
             algorithm::SECP256K1,
             sec_key.as_slice(),
-        );
+        )
+        .unwrap();
         let result = tx.verify();
         assert!(result.is_ok());
 
@@ -356,7 +551,7 @@ mod test {
             .body(tx_string)
             .send()
             .unwrap();
-        dbg!(res.text());
+        res.text().unwrap();
     }
 
     #[test]
@@ -381,19 +576,20 @@ mod test {
             publisher_sigs: vec![],
             signers: vec![],
             signatures: vec![],
+            referred_tx: vec![],
         };
 
         // let data: Vec<u8> = tx.to_serialize_data().unwrap();
         let sec_key = bs58::decode("2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1").into_vec().unwrap();
         // let sec_key = base64::decode("2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1").unwrap();
-        tx.sign("admin".to_string(), algorithm::ED25519, sec_key.as_slice());
+        tx.sign("admin".to_string(), algorithm::ED25519, sec_key.as_slice())
+            .unwrap();
         // let s = String::from_utf8(data.clone());
         // dbg!(hex::encode(data.as_slice()));
         let result = tx.verify();
         assert!(result.is_ok());
 
-        let tx_string = serde_json::to_string_pretty(&tx).unwrap();
-        dbg!(tx_string);
+        serde_json::to_string_pretty(&tx).unwrap();
         // create a SHA3-256 object
         // let mut hasher = Sha3_256::new();
         // "Fpl2AbiSgVxJzhOU1ASofiYoLf0uqXlIWz0hXroxd0i38BfJVErzVdR7mQP1SEXk1sKz98i+fPDyPmRY56WbDA=="
@@ -412,7 +608,7 @@ mod test {
 
     #[test]
     fn test_no_std_serialize() {
-        let mut tx = Tx {
+        let tx = Tx {
             time: 1544709662543340000,
             expiration: 1544709692318715000,
             gas_ratio: 1.0,
@@ -431,11 +627,32 @@ mod test {
             publisher: "".to_string(),
             publisher_sigs: vec![],
             signers: vec![],
-            signatures: vec![]
+            signatures: vec![],
+            referred_tx: vec![]
         };
         let result = tx.no_std_serialize();
         // println!("{}", String::from_utf8_lossy(&result[..]));
-        dbg!(result);
+        assert!(result.contains(r#""chain_id": 1024,"#));
+    }
+
+    #[test]
+    fn should_no_std_serialize_fractional_gas() {
+        let mut tx = Tx::builder()
+            .action(IostAction::transfer("alice", "bob", "10", "").unwrap())
+            .gas_ratio(1.5)
+            .gas_limit(100_000.25)
+            .build()
+            .unwrap();
+        let body = tx.clone().no_std_serialize();
+        assert!(body.contains(r#""gas_ratio": 1.5,"#), "{}", body);
+        assert!(body.contains(r#""gas_limit": 100000.25,"#), "{}", body);
+
+        tx.gas_ratio = 2.0;
+        tx.gas_limit = 300_000.1;
+        let body = tx.no_std_serialize();
+        assert!(body.contains(r#""gas_ratio": 2,"#), "{}", body);
+        // truncated to hundredths, as in the signed bytes
+        assert!(body.contains(r#""gas_limit": 300000.09,"#), "{}", body);
     }
 
     #[test]
@@ -459,7 +676,8 @@ mod test {
             publisher: "".to_string(),
             publisher_sigs: vec![],
             signers: vec![],
-            signatures: vec![]
+            signatures: vec![],
+            referred_tx: vec![]
         };
 
         let sec_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
@@ -467,7 +685,8 @@ mod test {
             "testaccount".to_string(),
             algorithm::ED25519,
             sec_key.as_slice(),
-        );
+        )
+        .unwrap();
         assert!(tx.verify().is_ok());
 
         let tx_str = r#"
@@ -515,4 +734,139 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn should_co_sign_tx() {
+        let sec_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![IostAction::transfer("testaccount", "anothertest", "100", "").unwrap()],
+        );
+        assert!(tx.add_signer("bob").is_err());
+        tx.add_signer("bob@active").unwrap();
+        tx.add_signer("bob@active").unwrap();
+        assert_eq!(tx.signers, vec!["bob@active".to_string()]);
+        assert!(matches!(tx.verify(), Err(MissingSignerSignature())));
+        assert!(tx
+            .sign_as_signer("alice@active", algorithm::ED25519, &sec_key)
+            .is_err());
+
+        tx.sign_as_signer("bob@active", algorithm::ED25519, &sec_key)
+            .unwrap();
+        tx.sign(
+            "testaccount".to_string(),
+            algorithm::ED25519,
+            sec_key.as_slice(),
+        )
+        .unwrap();
+        assert!(tx.verify().is_ok());
+        // signing again replaces the publisher signature, which the node would reject twice
+        let publisher_sig = tx.publisher_sigs[0].signature.clone();
+        tx.sign("testaccount".to_string(), "ed25519", &sec_key)
+            .unwrap();
+        assert_eq!(tx.publisher_sigs.len(), 1);
+        assert_eq!(tx.publisher_sigs[0].signature, publisher_sig);
+        let key = KeySigner::ed25519(&sec_key).unwrap();
+        assert!(crate::transport::block_on(tx.sign_with("anothertest", &key)).is_err());
+        assert_eq!(tx.publisher, "testaccount");
+        assert!(tx.verify().is_ok());
+        assert!(tx.add_signer("carol@active").is_err());
+        assert!(tx
+            .sign_as_signer("bob@active", algorithm::ED25519, &sec_key)
+            .is_err());
+
+        // one key can't stand in for two signers
+        let mut twice = Tx::new(0, 0, 1024, tx.actions.clone());
+        twice.add_signer("bob@active").unwrap();
+        twice.add_signer("carol@active").unwrap();
+        twice
+            .sign_as_signer("bob@active", algorithm::ED25519, &sec_key)
+            .unwrap();
+        assert!(matches!(
+            twice.sign_as_signer("carol@active", algorithm::ED25519, &sec_key),
+            Err(DuplicateSignerKey(_))
+        ));
+        twice.signatures.push(twice.signatures[0].clone());
+        assert!(matches!(twice.verify(), Err(DuplicateSignerKey(_))));
+
+        let signed = tx.customized_to_serialize_data(true).unwrap();
+        let unsigned = tx.customized_to_serialize_data(false).unwrap();
+        // signature count, then algorithm byte, 64 byte signature and 32 byte public key, each
+        // prefixed with its length
+        assert_eq!(signed.len(), unsigned.len() + 4 + 4 + 1 + 4 + 64 + 4 + 32);
+        assert_eq!(
+            &signed[unsigned.len() + 8..unsigned.len() + 13],
+            &[2, 0, 0, 0, 64]
+        );
+
+        let mut tampered = tx.clone();
+        tampered.signatures[0].signature = tx.publisher_sigs[0].signature.clone();
+        assert!(matches!(tampered.verify(), Err(InvalidSignature())));
+        let mut tampered = tx.clone();
+        tampered.signers[0] = "carol@active".to_string();
+        assert!(tampered.verify().is_err());
+    }
+
+    /// The transaction of `should_tx_sign_be_ok`, signed by testaccount.
+    fn signed_transfer() -> Tx {
+        let sec_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![IostAction {
+                contract: b"token.iost".to_vec(),
+                action_name: b"transfer".to_vec(),
+                data: br#"["iost", "testaccount", "anothertest", "100", "this is an example transfer"]"#.to_vec(),
+            }],
+        );
+        tx.gas_limit = 500000.0;
+        tx.sign("testaccount".to_string(), algorithm::ED25519, &sec_key)
+            .unwrap();
+        tx
+    }
+
+    #[test]
+    fn should_hash_tx() {
+        // The expected hashes come from go-iost and mainnet, and are checked by the conformance
+        // vectors; here only what the hash covers.
+        let tx = signed_transfer();
+        assert_eq!(
+            tx.publisher_sigs[0].signature,
+            "/K1HM0OEbfJ4+D3BmalpLmb03WS7BeCz4nVHBNbDrx3/A31aN2RJNxyEKhv+VSoWctfevDNRnL1kadRVxSt8CA=="
+        );
+        let hash = tx.hash().unwrap();
+        assert_eq!(hash.as_bytes().len(), 32);
+
+        let mut other = tx.clone();
+        other.publisher = "anothertest".to_string();
+        assert_ne!(other.hash().unwrap(), hash);
+        let mut other = tx;
+        other.publisher_sigs.clear();
+        assert_ne!(other.hash().unwrap(), hash);
+    }
+
+    #[test]
+    fn should_hash_deferred_tx() {
+        let sec_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
+        let mut delayed = signed_transfer();
+        delayed.delay = 60_000_000_000;
+        delayed
+            .sign("testaccount".to_string(), algorithm::ED25519, &sec_key)
+            .unwrap();
+        let deferred = delayed.deferred().unwrap();
+        assert_eq!(deferred.referred_tx.len(), 32);
+
+        let hash = deferred.hash().unwrap();
+        assert_ne!(hash, delayed.hash().unwrap());
+        assert_eq!(
+            deferred.referred_tx,
+            delayed.hash().unwrap().as_bytes().to_vec()
+        );
+        let mut unreferred = deferred;
+        unreferred.referred_tx.clear();
+        assert_ne!(unreferred.hash().unwrap(), hash);
+    }
 }
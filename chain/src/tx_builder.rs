@@ -0,0 +1,312 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use crate::tx::is_account_permission;
+use crate::validate::{is_amount, is_token};
+use crate::{AmountLimit, Error, IostAction, Result, Tx};
+
+pub use crate::estimate::MIN_GAS_LIMIT;
+
+/// lowest gas ratio a node accepts
+pub const MIN_GAS_RATIO: f64 = 1.0;
+/// highest gas ratio a node accepts
+pub const MAX_GAS_RATIO: f64 = 100.0;
+/// chain ID of the IOST mainnet
+pub const MAINNET_CHAIN_ID: u32 = 1024;
+
+/// Builds a [`Tx`], checking the rules a node enforces before it is signed.
+///
+/// Defaults to gas ratio 1, gas limit 1,000,000, no delay, the mainnet chain ID, an
+/// expiration 90 seconds after `time`, and no amount limit besides `*: unlimited`.
+///
+/// ```
+/// # use iost_chain::{IostAction, TxBuilder};
+/// let tx = TxBuilder::new()
+///     .action(IostAction::transfer("alice", "bob", "10", "").unwrap())
+///     .publisher("alice")
+///     .time(1_600_000_000_000_000_000)
+///     .gas_limit(100_000.0)
+///     .build()
+///     .unwrap();
+/// assert_eq!(tx.expiration, 1_600_000_090_000_000_000);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TxBuilder {
+    actions: Vec<IostAction>,
+    publisher: String,
+    signers: Vec<String>,
+    gas_ratio: f64,
+    gas_limit: f64,
+    delay: Duration,
+    time: Option<i64>,
+    expiration: Expiration,
+    amount_limits: Vec<AmountLimit>,
+    chain_id: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Expiration {
+    After(Duration),
+    At(i64),
+}
+
+impl Default for TxBuilder {
+    fn default() -> Self {
+        TxBuilder {
+            actions: vec![],
+            publisher: String::new(),
+            signers: vec![],
+            gas_ratio: 1.0,
+            gas_limit: 1_000_000.0,
+            delay: Duration::from_secs(0),
+            time: None,
+            expiration: Expiration::After(Duration::from_secs(90)),
+            amount_limits: vec![],
+            chain_id: MAINNET_CHAIN_ID,
+        }
+    }
+}
+
+impl TxBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn action(mut self, action: IostAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    pub fn actions<I: IntoIterator<Item = IostAction>>(mut self, actions: I) -> Self {
+        self.actions.extend(actions);
+        self
+    }
+
+    /// Account that pays for and signs the transaction.
+    pub fn publisher(mut self, publisher: &str) -> Self {
+        self.publisher = publisher.to_string();
+        self
+    }
+
+    /// Add a signer besides the publisher, as `account@permission`, e.g. `bob@active`.
+    pub fn signer(mut self, signer: &str) -> Self {
+        self.signers.push(signer.to_string());
+        self
+    }
+
+    pub fn gas_ratio(mut self, gas_ratio: f64) -> Self {
+        self.gas_ratio = gas_ratio;
+        self
+    }
+
+    pub fn gas_limit(mut self, gas_limit: f64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Execute the transaction `delay` after it is packed.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Time of the transaction in nanoseconds since the Unix epoch. Defaults to now with
+    /// `std`, and is required without it.
+    pub fn time(mut self, time: i64) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Expire the transaction `window` after its time.
+    pub fn expire_after(mut self, window: Duration) -> Self {
+        self.expiration = Expiration::After(window);
+        self
+    }
+
+    /// Expire the transaction at `expiration`, in nanoseconds since the Unix epoch.
+    pub fn expiration(mut self, expiration: i64) -> Self {
+        self.expiration = Expiration::At(expiration);
+        self
+    }
+
+    /// Allow the transaction to spend at most `value` of `token`, e.g. `("iost", "100")`.
+    /// Replaces the default `*: unlimited`.
+    pub fn amount_limit(mut self, token: &str, value: &str) -> Self {
+        self.amount_limits.push(AmountLimit {
+            token: token.to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    pub fn chain_id(mut self, chain_id: u32) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Build the unsigned transaction, or fail with `Error::InvalidTx` naming the broken rule.
+    pub fn build(self) -> Result<Tx> {
+        if self.actions.is_empty() {
+            return Err(invalid("no actions"));
+        }
+        if !(MIN_GAS_RATIO..=MAX_GAS_RATIO).contains(&self.gas_ratio) {
+            return Err(invalid(format!(
+                "gas ratio {} is outside [{}, {}]",
+                self.gas_ratio, MIN_GAS_RATIO, MAX_GAS_RATIO
+            )));
+        }
+        if !(MIN_GAS_LIMIT..).contains(&self.gas_limit) {
+            return Err(invalid(format!(
+                "gas limit {} is below {}",
+                self.gas_limit, MIN_GAS_LIMIT
+            )));
+        }
+        if let Some(signer) = self.signers.iter().find(|s| !is_account_permission(s)) {
+            return Err(invalid(format!(
+                "signer {} is not account@permission",
+                signer
+            )));
+        }
+        if let Some(limit) = self
+            .amount_limits
+            .iter()
+            .find(|l| !is_token(&l.token) || !is_amount(&l.value))
+        {
+            return Err(invalid(format!(
+                "amount limit {}: {} is not a token and an amount",
+                limit.token, limit.value
+            )));
+        }
+        let time = match self.time {
+            Some(time) => time,
+            #[cfg(feature = "std")]
+            None => chrono::Utc::now().timestamp_nanos(),
+            #[cfg(not(feature = "std"))]
+            None => return Err(invalid("no time")),
+        };
+        let expiration = match self.expiration {
+            Expiration::After(window) => time.saturating_add(nanos(window)?),
+            Expiration::At(expiration) => expiration,
+        };
+        if expiration <= time {
+            return Err(invalid(format!(
+                "expiration {} is not after time {}",
+                expiration, time
+            )));
+        }
+        let amount_limit = if self.amount_limits.is_empty() {
+            vec![AmountLimit {
+                token: "*".to_string(),
+                value: "unlimited".to_string(),
+            }]
+        } else {
+            self.amount_limits
+        };
+
+        Ok(Tx {
+            time,
+            expiration,
+            gas_ratio: self.gas_ratio,
+            gas_limit: self.gas_limit,
+            delay: nanos(self.delay)?,
+            chain_id: self.chain_id,
+            actions: self.actions,
+            amount_limit,
+            publisher: self.publisher,
+            publisher_sigs: vec![],
+            signers: self.signers,
+            signatures: vec![],
+            referred_tx: vec![],
+        })
+    }
+}
+
+impl Tx {
+    pub fn builder() -> TxBuilder {
+        TxBuilder::new()
+    }
+}
+
+fn invalid<S: Into<String>>(rule: S) -> Error {
+    Error::InvalidTx(rule.into())
+}
+
+fn nanos(duration: Duration) -> Result<i64> {
+    let nanos = duration.as_nanos();
+    if nanos > i64::MAX as u128 {
+        return Err(invalid(format!("duration {:?} is too long", duration)));
+    }
+    Ok(nanos as i64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn transfer() -> TxBuilder {
+        TxBuilder::new()
+            .action(IostAction::transfer("alice", "bob", "10", "").unwrap())
+            .publisher("alice")
+            .time(1_000)
+    }
+
+    fn rule(builder: TxBuilder) -> String {
+        match builder.build() {
+            Err(Error::InvalidTx(rule)) => rule,
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_build_tx() {
+        let tx = transfer()
+            .signer("bob@active")
+            .gas_ratio(2.0)
+            .gas_limit(50_000.0)
+            .delay(Duration::from_secs(1))
+            .expire_after(Duration::from_nanos(500))
+            .amount_limit("iost", "10")
+            .chain_id(1023)
+            .build()
+            .unwrap();
+        assert_eq!(tx.time, 1_000);
+        assert_eq!(tx.expiration, 1_500);
+        assert_eq!(tx.delay, 1_000_000_000);
+        assert_eq!(tx.gas_ratio, 2.0);
+        assert_eq!(tx.gas_limit, 50_000.0);
+        assert_eq!(tx.chain_id, 1023);
+        assert_eq!(tx.publisher, "alice");
+        assert_eq!(tx.signers, vec!["bob@active".to_string()]);
+        assert_eq!(tx.amount_limit.len(), 1);
+        assert_eq!(tx.amount_limit[0].token, "iost");
+
+        let tx = transfer().build().unwrap();
+        assert_eq!(tx.chain_id, MAINNET_CHAIN_ID);
+        assert_eq!(tx.amount_limit[0].token, "*");
+        assert_eq!(tx.amount_limit[0].value, "unlimited");
+    }
+
+    #[test]
+    fn should_reject_broken_rules() {
+        assert_eq!(rule(TxBuilder::new().time(1_000)), "no actions");
+        assert!(rule(transfer().gas_ratio(0.5)).starts_with("gas ratio"));
+        assert!(rule(transfer().gas_ratio(100.5)).starts_with("gas ratio"));
+        assert!(rule(transfer().gas_ratio(f64::NAN)).starts_with("gas ratio"));
+        assert!(rule(transfer().gas_limit(49_999.0)).starts_with("gas limit"));
+        assert!(rule(transfer().gas_limit(f64::NAN)).starts_with("gas limit"));
+        assert!(rule(transfer().expiration(1_000)).starts_with("expiration"));
+        assert!(rule(transfer().signer("bob")).starts_with("signer bob"));
+        assert!(rule(transfer().signer("bob@active@owner")).starts_with("signer"));
+        assert!(rule(transfer().amount_limit("IOST", "10")).starts_with("amount limit"));
+        assert!(rule(transfer().amount_limit("iost", "-1")).starts_with("amount limit"));
+        assert!(rule(transfer().amount_limit("iost", "1.")).starts_with("amount limit"));
+        assert!(transfer()
+            .gas_ratio(100.0)
+            .expiration(1_001)
+            .build()
+            .is_ok());
+    }
+}
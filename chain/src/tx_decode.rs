@@ -0,0 +1,242 @@
+//! Decoding of the binary form written by `Tx::customized_to_serialize_data`, so payloads
+//! signed elsewhere can be inspected and verified again.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{AmountLimit, Error, IostAction, Read, Result, Signature, Tx};
+
+/// Reads the go-iost encoding: big endian integers, and byte strings and lists prefixed with
+/// their length. Lengths are checked against the remaining input before anything is allocated.
+pub(crate) struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Decoder { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.bytes.len() - self.pos {
+            return Err(invalid(format!(
+                "{} bytes needed at {}, {} left",
+                len,
+                self.pos,
+                self.bytes.len() - self.pos
+            )));
+        }
+        let bytes = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn number<T: Read>(&mut self, width: usize) -> Result<T> {
+        T::read(self.take(width)?, &mut 0).map_err(Error::BytesReadError)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8> {
+        self.number(1)
+    }
+
+    pub(crate) fn i32(&mut self) -> Result<i32> {
+        self.number(4)
+    }
+
+    pub(crate) fn i64(&mut self) -> Result<i64> {
+        self.number(8)
+    }
+
+    /// A length prefix, which can't exceed the remaining input since every item takes at
+    /// least a byte.
+    fn len(&mut self) -> Result<usize> {
+        let len = self.i32()?;
+        if len < 0 || len as usize > self.bytes.len() - self.pos {
+            return Err(invalid(format!(
+                "invalid length {} at {}",
+                len,
+                self.pos - 4
+            )));
+        }
+        Ok(len as usize)
+    }
+
+    pub(crate) fn bytes(&mut self) -> Result<Vec<u8>> {
+        let len = self.len()?;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    pub(crate) fn string(&mut self) -> Result<String> {
+        String::from_utf8(self.bytes()?).map_err(|_| invalid("string is not UTF-8"))
+    }
+
+    /// A list whose items are decoded by `decode` from their own length-prefixed bytes.
+    pub(crate) fn items<T>(
+        &mut self,
+        mut decode: impl FnMut(&mut Decoder<'a>) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let count = self.len()?;
+        let mut items = Vec::with_capacity(count);
+        for _ in 0..count {
+            let len = self.len()?;
+            let mut item = Decoder::new(self.take(len)?);
+            items.push(decode(&mut item)?);
+            item.finish()?;
+        }
+        Ok(items)
+    }
+
+    /// Fail unless the whole input was read.
+    pub(crate) fn finish(&self) -> Result<()> {
+        if self.pos != self.bytes.len() {
+            return Err(invalid(format!(
+                "{} trailing bytes",
+                self.bytes.len() - self.pos
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn invalid<S: Into<String>>(reason: S) -> Error {
+    Error::TxDecodeError(reason.into())
+}
+
+impl Tx {
+    /// Decode `customized_to_serialize_data(with_sign)`. The publisher and its signatures
+    /// aren't part of that form, so they are left empty.
+    pub fn decode(bytes: &[u8], with_sign: bool) -> Result<Tx> {
+        let mut decoder = Decoder::new(bytes);
+        let time = decoder.i64()?;
+        let expiration = decoder.i64()?;
+        let gas_ratio = decoder.i64()? as f64 / 100.0;
+        let gas_limit = decoder.i64()? as f64 / 100.0;
+        let delay = decoder.i64()?;
+        let chain_id = decoder.i32()? as u32;
+        let reserved = decoder.i32()?;
+        if reserved != 0 {
+            return Err(invalid(format!("reserved field is {}, not 0", reserved)));
+        }
+
+        let signer_count = decoder.len()?;
+        let mut signers = Vec::with_capacity(signer_count);
+        for _ in 0..signer_count {
+            signers.push(decoder.string()?);
+        }
+        let actions = decoder.items(|item| {
+            Ok(IostAction {
+                contract: item.bytes()?,
+                action_name: item.bytes()?,
+                data: item.bytes()?,
+            })
+        })?;
+        let amount_limit = decoder.items(|item| {
+            Ok(AmountLimit {
+                token: item.string()?,
+                value: item.string()?,
+            })
+        })?;
+        let signatures = if with_sign {
+            decoder.items(Signature::decode)?
+        } else {
+            Vec::new()
+        };
+        decoder.finish()?;
+
+        Ok(Tx {
+            time,
+            expiration,
+            gas_ratio,
+            gas_limit,
+            delay,
+            chain_id,
+            actions,
+            amount_limit,
+            publisher: String::new(),
+            publisher_sigs: Vec::new(),
+            signers,
+            signatures,
+            referred_tx: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use keys::algorithm;
+
+    fn co_signed() -> Tx {
+        let sec_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1023,
+            vec![IostAction::transfer("alice", "bob", "10", "memo").unwrap()],
+        );
+        tx.gas_ratio = 1.5;
+        tx.gas_limit = 123456.5;
+        tx.delay = 7;
+        tx.amount_limit.push(AmountLimit {
+            token: "iost".to_string(),
+            value: "10".to_string(),
+        });
+        tx.add_signer("bob@active").unwrap();
+        tx.sign_as_signer("bob@active", algorithm::ED25519, &sec_key)
+            .unwrap();
+        tx
+    }
+
+    #[test]
+    fn should_invert_customized_write() {
+        let tx = co_signed();
+        for &with_sign in &[false, true] {
+            let bytes = tx.customized_to_serialize_data(with_sign).unwrap();
+            let decoded = Tx::decode(&bytes, with_sign).unwrap();
+            assert_eq!(
+                decoded.customized_to_serialize_data(with_sign).unwrap(),
+                bytes
+            );
+            assert_eq!(decoded.time, tx.time);
+            assert_eq!(decoded.expiration, tx.expiration);
+            assert_eq!(decoded.gas_ratio, 1.5);
+            assert_eq!(decoded.gas_limit, 123456.5);
+            assert_eq!(decoded.delay, 7);
+            assert_eq!(decoded.chain_id, 1023);
+            assert_eq!(decoded.signers, tx.signers);
+            assert_eq!(decoded.actions[0].data, tx.actions[0].data);
+            assert_eq!(decoded.amount_limit[1].token, "iost");
+            assert_eq!(decoded.signatures.len(), if with_sign { 1 } else { 0 });
+        }
+
+        let bytes = tx.customized_to_serialize_data(true).unwrap();
+        let decoded = Tx::decode(&bytes, true).unwrap();
+        assert_eq!(decoded.signatures[0].algorithm, algorithm::ED25519);
+        assert_eq!(decoded.signatures[0].signature, tx.signatures[0].signature);
+        assert_eq!(
+            decoded.signatures[0].public_key,
+            tx.signatures[0].public_key
+        );
+        assert!(decoded.verify().is_ok());
+    }
+
+    #[test]
+    fn should_reject_malformed_bytes() {
+        let bytes = co_signed().customized_to_serialize_data(true).unwrap();
+        for len in 0..bytes.len() {
+            assert!(Tx::decode(&bytes[..len], true).is_err());
+        }
+        assert!(Tx::decode(&bytes, false).is_err());
+
+        let mut huge = bytes[..48].to_vec();
+        huge.extend_from_slice(&[0x7f, 0xff, 0xff, 0xff]);
+        match Tx::decode(&huge, false) {
+            Err(Error::TxDecodeError(reason)) => assert!(reason.starts_with("invalid length")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::string::{String, ToString};
+use core::convert::TryInto;
+use core::fmt;
+use core::str::FromStr;
+
+use sha3::{Digest, Sha3_256};
+
+use crate::Error;
+
+/// Hash of a transaction: SHA3-256 of its full serialized form. Displayed in base58, like the
+/// `hash` returned by `sendTx` and taken by `getTxByHash`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TxHash(pub [u8; 32]);
+
+impl TxHash {
+    /// SHA3-256 of `data`.
+    pub fn digest(data: &[u8]) -> Self {
+        let mut hasher = Sha3_256::new();
+        hasher.input(data);
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(hasher.result().as_slice());
+        TxHash(hash)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for TxHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&bs58::encode(self.0).into_string())
+    }
+}
+
+impl fmt::Debug for TxHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TxHash({})", self)
+    }
+}
+
+impl FromStr for TxHash {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = bs58::decode(s)
+            .into_vec()
+            .map_err(|_| Error::InvalidTxHash(s.to_string()))?;
+        let bytes: [u8; 32] = bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| Error::InvalidTxHash(s.to_string()))?;
+        Ok(TxHash(bytes))
+    }
+}
+
+impl From<TxHash> for String {
+    fn from(hash: TxHash) -> Self {
+        hash.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_round_trip_base58() {
+        let hash: TxHash = "Dj8bmA4Fx4LHrwLtDB6EEkNbBFU8biENxf55mNaJewYw"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            hash.to_string(),
+            "Dj8bmA4Fx4LHrwLtDB6EEkNbBFU8biENxf55mNaJewYw"
+        );
+        assert!("Dj8bmA4Fx4LHrwLtDB6EEkNbBFU8biENxf55mNaJew"
+            .parse::<TxHash>()
+            .is_err());
+        assert!("0OIl".parse::<TxHash>().is_err());
+    }
+}
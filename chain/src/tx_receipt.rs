@@ -1,47 +1,215 @@
 #![allow(dead_code)]
 
-#[cfg(feature = "std")]
-use serde::{Deserialize, Serialize};
-
 use alloc::collections::btree_map::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
 
+use lite_json::JsonValue;
+#[cfg(feature = "std")]
+use serde::Serialize;
+
+#[cfg(feature = "std")]
+use crate::de::{value_to_f64, value_to_i64};
+use crate::json::{JsonObject, NoStdDeserialize};
+use crate::StatusCode;
 use crate::{spv::tx::TxReceiptStatus, Receipt};
 
-#[derive(Debug)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "std", derive(Serialize))]
 pub struct TxReceipt {
-    #[cfg(feature = "std")]
-    #[serde(rename = "txHash")]
+    /// hash of the transaction
+    #[cfg_attr(feature = "std", serde(rename = "txHash"))]
     pub tx_hash: String,
-    #[cfg(feature = "std")]
-    #[serde(rename = "gasUsage")]
-    pub gas_usage: String,
-    #[cfg(feature = "std")]
-    #[serde(rename = "ramUsage")]
-    pub ram_usage: BTreeMap<String, String>,
+    /// GAS consumption of the transaction
+    #[cfg_attr(feature = "std", serde(rename = "gasUsage"))]
+    pub gas_usage: f64,
+    /// RAM consumption for the transaction. map-key is account name, and value is RAM amount
+    #[cfg_attr(feature = "std", serde(rename = "ramUsage"))]
+    pub ram_usage: BTreeMap<String, i64>,
+    /// Status of the transaction, `code` is the numeric value of `StatusCode`
     pub status: TxReceiptStatus,
+    /// return values for each Action
     pub returns: Vec<String>,
+    /// for event functions
     pub receipts: Vec<Receipt>,
 }
-// pub struct TxReceipt {
-//     /// hash of the transaction
-//     pub tx_hash: String,
-//     /// GAS consumption of the transaction
-//     pub gas_usage: f64,
-//     /// RAM consumption for the transaction. map-key is account name, and value is RAM amount
-//     pub ram_usage: BTreeMap<String, i64>,
-//     /// Status of the transaction. SUCCESS; GAS_RUN_OUT - insufficient GAS;
-//     /// BALANCE_NOT_ENOUGH - insufficient balance; WRONG_PARAMETER;
-//     /// RUNTIME_ERROR - a run-time error; TIMEOUT; WRONG_TX_FORMAT;
-//     /// DUPLICCATE_SET_CODE - set code is duplicated unexpectedly;
-//     /// UNKNOWN_ERROR
-//     pub status_code: StatusCode,
-//     /// a message descripting status_code
-//     pub message: String,
-//     /// return values for each Action
-//     pub returns: Vec<String>,
-//     /// for event functions
-//     pub receipts: Vec<Receipt>,
-// }
+
+/// Receipts come in two shapes: the block format (`txHash`, `status: {code, message}`)
+/// and the HTTP API format (`tx_hash`, `status_code: "SUCCESS"`, `message`). Accept both.
+#[cfg(feature = "std")]
+impl<'de> serde::Deserialize<'de> for TxReceipt {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        #[derive(Debug)]
+        struct VisitorTxReceipt;
+        impl<'de> serde::de::Visitor<'de> for VisitorTxReceipt {
+            type Value = TxReceipt;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "string or a struct, but this is: {:?}", self)
+            }
+
+            fn visit_map<D>(self, mut map: D) -> Result<Self::Value, D::Error>
+            where
+                D: serde::de::MapAccess<'de>,
+            {
+                let mut receipt = TxReceipt::default();
+                while let Some(field) = map.next_key::<String>()? {
+                    match field.as_str() {
+                        "txHash" | "tx_hash" => {
+                            receipt.tx_hash = map.next_value()?;
+                        }
+                        "gasUsage" | "gas_usage" => {
+                            let value: serde_json::Value = map.next_value()?;
+                            receipt.gas_usage = value_to_f64(&value)
+                                .ok_or_else(|| serde::de::Error::custom("invalid gas usage"))?;
+                        }
+                        "ramUsage" | "ram_usage" => {
+                            let values: BTreeMap<String, serde_json::Value> = map.next_value()?;
+                            for (account, value) in values {
+                                let ram = value_to_i64(&value)
+                                    .ok_or_else(|| serde::de::Error::custom("invalid ram usage"))?;
+                                receipt.ram_usage.insert(account, ram);
+                            }
+                        }
+                        "status" => {
+                            receipt.status = map.next_value()?;
+                        }
+                        "status_code" => {
+                            let status_code: StatusCode = map.next_value()?;
+                            receipt.status.code = status_code.code();
+                        }
+                        "message" => {
+                            receipt.status.message = map.next_value()?;
+                        }
+                        "returns" => {
+                            receipt.returns = map.next_value()?;
+                        }
+                        "receipts" => {
+                            receipt.receipts = map.next_value()?;
+                        }
+                        _ => {
+                            let _: serde_json::Value = map.next_value()?;
+                            continue;
+                        }
+                    }
+                }
+                Ok(receipt)
+            }
+        }
+        deserializer.deserialize_any(VisitorTxReceipt)
+    }
+}
+
+impl NoStdDeserialize for TxReceipt {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        let tx_hash = match object.get("txHash") {
+            Some(value) => String::no_std_deserialize(value)?,
+            None => object.field("tx_hash")?,
+        };
+        let gas_usage = match object.get("gasUsage") {
+            Some(value) => f64::no_std_deserialize(value)?,
+            None => object.field_or_default("gas_usage")?,
+        };
+        let ram_usage = match object.get("ramUsage") {
+            Some(value) => BTreeMap::no_std_deserialize(value)?,
+            None => object.field_or_default("ram_usage")?,
+        };
+        let status = match object.get("status") {
+            Some(value) => {
+                let status = JsonObject::new(value)?;
+                TxReceiptStatus {
+                    code: status.field("code")?,
+                    message: status.field_or_default("message")?,
+                }
+            }
+            None => TxReceiptStatus {
+                code: object
+                    .field_or_default::<Option<StatusCode>>("status_code")?
+                    .map(StatusCode::code)
+                    .unwrap_or_default(),
+                message: object.field_or_default("message")?,
+            },
+        };
+        Ok(TxReceipt {
+            tx_hash,
+            gas_usage,
+            ram_usage,
+            status,
+            returns: object.field_or_default("returns")?,
+            receipts: object.field_or_default("receipts")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_deserialize_api_receipt() {
+        let receipt_str = r#"
+        {
+            "tx_hash": "Dj8bmA4Fx4LHrwLtDB6EEkNbBFU8biENxf55mNaJewYw",
+            "gas_usage": 2577,
+            "ram_usage": {"admin": "14", "lispczz3": 0},
+            "status_code": "BALANCE_NOT_ENOUGH",
+            "message": "balance not enough",
+            "returns": ["[]"],
+            "receipts": [{"func_name": "token.iost/transfer", "content": "[]"}]
+        }
+        "#;
+        let receipt: TxReceipt = serde_json::from_str(receipt_str).unwrap();
+        assert_eq!(
+            receipt.tx_hash,
+            "Dj8bmA4Fx4LHrwLtDB6EEkNbBFU8biENxf55mNaJewYw"
+        );
+        assert_eq!(receipt.gas_usage, 2577.0);
+        assert_eq!(receipt.ram_usage.get("admin"), Some(&14));
+        assert_eq!(receipt.status.code, StatusCode::BALANCE_NOT_ENOUGH.code());
+        assert_eq!(receipt.status.message, "balance not enough");
+        assert_eq!(receipt.receipts[0].func_name, "token.iost/transfer");
+    }
+
+    #[test]
+    fn should_deserialize_block_receipt() {
+        let receipt_str = r#"
+        {
+            "txHash": "Dj8bmA4Fx4LHrwLtDB6EEkNbBFU8biENxf55mNaJewYw",
+            "gasUsage": "2577",
+            "ramUsage": {},
+            "status": {"code": 0, "message": ""},
+            "returns": [],
+            "receipts": [{"funcName": "vote_producer.iost/stat", "content": "{}"}]
+        }
+        "#;
+        let receipt: TxReceipt = serde_json::from_str(receipt_str).unwrap();
+        assert_eq!(receipt.gas_usage, 2577.0);
+        assert_eq!(receipt.status.code, 0);
+        assert_eq!(receipt.receipts[0].func_name, "vote_producer.iost/stat");
+    }
+
+    #[test]
+    fn should_no_std_deserialize_api_receipt() {
+        let receipt_str = r#"
+        {
+            "tx_hash": "Dj8bmA4Fx4LHrwLtDB6EEkNbBFU8biENxf55mNaJewYw",
+            "gas_usage": 2577,
+            "ram_usage": {"admin": "14", "lispczz3": 0},
+            "status_code": "BALANCE_NOT_ENOUGH",
+            "message": "balance not enough",
+            "returns": ["[]"],
+            "receipts": [{"func_name": "token.iost/transfer", "content": "[]"}]
+        }
+        "#;
+        let receipt: TxReceipt = crate::json::from_slice(receipt_str.as_bytes()).unwrap();
+        assert_eq!(receipt.gas_usage, 2577.0);
+        assert_eq!(receipt.ram_usage.get("admin"), Some(&14));
+        assert_eq!(receipt.status.code, StatusCode::BALANCE_NOT_ENOUGH.code());
+        assert_eq!(receipt.status.message, "balance not enough");
+        assert_eq!(receipt.receipts[0].func_name, "token.iost/transfer");
+    }
+}
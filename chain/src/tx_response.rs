@@ -1,6 +1,8 @@
 use alloc::string::String;
 
+use crate::json::{JsonObject, NoStdDeserialize};
 use crate::tx_receipt::TxReceipt;
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
@@ -10,5 +12,16 @@ pub struct TxResponse {
     /// Hash of transaction
     pub hash: String,
     /// The receipt of the transaction pre executed by the RPC node requires the RPC node to turn on the pre execution switch to return this field
-    pub pre_tx_receipt: TxReceipt,
+    #[cfg_attr(feature = "std", serde(default))]
+    pub pre_tx_receipt: Option<TxReceipt>,
+}
+
+impl NoStdDeserialize for TxResponse {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(TxResponse {
+            hash: object.field("hash")?,
+            pre_tx_receipt: object.field_or_default("pre_tx_receipt")?,
+        })
+    }
 }
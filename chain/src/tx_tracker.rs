@@ -0,0 +1,337 @@
+//! Waiting for a published transaction to be packed or become irreversible.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use core::time::Duration;
+
+use crate::node_error::is_transient;
+use crate::transport::HttpTransport;
+use crate::{Error, IostClient, NodeErrorKind, Result, Status, StatusCode, Tx, TxReceipt};
+
+/// How far a transaction has to get before it counts as confirmed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmationLevel {
+    /// in a block that may still be reverted
+    Packed,
+    /// in a block at or below the last irreversible block
+    Irreversible,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackerConfig {
+    pub level: ConfirmationLevel,
+    /// give up after this long. Measured with the clock in `std` builds, and as the sum of
+    /// poll intervals without it.
+    pub timeout: Duration,
+    /// wait between two polls of `getTxByHash`
+    pub poll_interval: Duration,
+}
+
+impl Default for TrackerConfig {
+    fn default() -> Self {
+        TrackerConfig {
+            level: ConfirmationLevel::Irreversible,
+            timeout: Duration::from_secs(90),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// A transaction that reached the requested confirmation level.
+#[derive(Debug, Clone)]
+pub struct Confirmation {
+    pub hash: String,
+    pub status: Status,
+    pub block_number: i64,
+    pub receipt: TxReceipt,
+}
+
+/// Polls `getTxByHash` until a transaction reaches the configured [`ConfirmationLevel`].
+///
+/// Fails with `Error::TxFailed` when the transaction was packed with a status code other than
+/// `SUCCESS`, with `Error::TxExpired` when the head block time passes the transaction's
+/// expiration before it was packed, and with `Error::TxConfirmTimeout` after the timeout.
+pub struct TxTracker<'a, T> {
+    client: &'a IostClient<T>,
+    config: TrackerConfig,
+}
+
+impl<'a, T: HttpTransport> TxTracker<'a, T> {
+    pub fn new(client: &'a IostClient<T>, config: TrackerConfig) -> Self {
+        TxTracker { client, config }
+    }
+
+    pub fn config(&self) -> &TrackerConfig {
+        &self.config
+    }
+
+    /// Wait for the transaction `hash`. `expiration` is `Tx.expiration` in nanoseconds; without
+    /// it an expired transaction only surfaces as a timeout.
+    pub async fn wait(&self, hash: &str, expiration: Option<i64>) -> Result<Confirmation> {
+        self.wait_until(hash, expiration, self.config.timeout).await
+    }
+
+    /// Wait for the deferred transaction the node creates from the delayed transaction `tx`,
+    /// see [`Tx::deferred`]. The timeout starts counting after the delay; a cancelled
+    /// transaction surfaces as `Error::TxExpired` once the deferred one would have expired.
+    pub async fn wait_deferred(&self, tx: &Tx) -> Result<Confirmation> {
+        let deferred = tx.deferred()?;
+        let delay = Duration::from_nanos(tx.delay as u64);
+        self.wait_until(
+            &deferred.hash()?.to_string(),
+            Some(deferred.expiration),
+            self.config.timeout + delay,
+        )
+        .await
+    }
+
+    async fn wait_until(
+        &self,
+        hash: &str,
+        expiration: Option<i64>,
+        timeout: Duration,
+    ) -> Result<Confirmation> {
+        let mut timer = Timer::start();
+        loop {
+            if let Some(confirmation) = self.poll(hash, expiration).await? {
+                return Ok(confirmation);
+            }
+            if timer.elapsed() + self.config.poll_interval > timeout {
+                return Err(Error::TxConfirmTimeout(hash.to_string()));
+            }
+            self.client
+                .transport()
+                .sleep(self.config.poll_interval)
+                .await;
+            timer.add(self.config.poll_interval);
+        }
+    }
+
+    /// One poll: the confirmation once the level is reached, `None` while waiting.
+    async fn poll(&self, hash: &str, expiration: Option<i64>) -> Result<Option<Confirmation>> {
+        let tx = match self.client.get_tx_by_hash(hash).await {
+            Ok(tx) => tx,
+            Err(Error::NodeError(NodeErrorKind::NotFound, _)) => {
+                self.check_expiration(hash, expiration).await?;
+                return Ok(None);
+            }
+            Err(err) if is_transient(&err) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        if tx.status == Status::PENDING {
+            self.check_expiration(hash, expiration).await?;
+            return Ok(None);
+        }
+        let receipt = match tx.transaction.tx_receipt {
+            Some(receipt) => receipt,
+            None => self.client.get_tx_receipt_by_tx_hash(hash).await?,
+        };
+        if receipt.status.code != StatusCode::SUCCESS.code() {
+            return Err(Error::TxFailed(Box::new(receipt)));
+        }
+        let reached = match self.config.level {
+            ConfirmationLevel::Packed => true,
+            ConfirmationLevel::Irreversible => tx.status == Status::IRREVERSIBLE,
+        };
+        if !reached {
+            return Ok(None);
+        }
+        Ok(Some(Confirmation {
+            hash: hash.to_string(),
+            status: tx.status,
+            block_number: tx.block_number.parse().unwrap_or_default(),
+            receipt,
+        }))
+    }
+
+    async fn check_expiration(&self, hash: &str, expiration: Option<i64>) -> Result<()> {
+        let expiration = match expiration {
+            Some(expiration) => expiration,
+            None => return Ok(()),
+        };
+        let head_block_time: i64 = match self.client.get_chain_info().await {
+            Ok(info) => info.head_block_time.parse().unwrap_or_default(),
+            Err(err) if is_transient(&err) => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if head_block_time > expiration {
+            return Err(Error::TxExpired(hash.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Time spent waiting, for enforcing [`TrackerConfig::timeout`].
+struct Timer {
+    #[cfg(feature = "std")]
+    started: std::time::Instant,
+    #[cfg(not(feature = "std"))]
+    slept: Duration,
+}
+
+impl Timer {
+    fn start() -> Self {
+        Timer {
+            #[cfg(feature = "std")]
+            started: std::time::Instant::now(),
+            #[cfg(not(feature = "std"))]
+            slept: Duration::from_secs(0),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn elapsed(&self) -> Duration {
+        self.slept
+    }
+
+    #[cfg(feature = "std")]
+    fn add(&mut self, _slept: Duration) {}
+
+    #[cfg(not(feature = "std"))]
+    fn add(&mut self, slept: Duration) {
+        self.slept += slept;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{fixtures, MockTransport, Reply};
+    use crate::transport::block_on;
+
+    /// head block time of the fixture chain
+    fn head_block_time() -> i64 {
+        let info = fixtures::chain_info(100, 90);
+        info["head_block_time"].as_str().unwrap().parse().unwrap()
+    }
+
+    fn tx(status: &str, status_code: &str) -> Reply {
+        let mut body = fixtures::tx_by_hash("abc", 1264, status);
+        body["transaction"]["tx_receipt"]["status_code"] = status_code.into();
+        Reply::ok(body)
+    }
+
+    fn not_found() -> Reply {
+        Reply::error(404, 5, "tx not found")
+    }
+
+    fn node(txs: Vec<Reply>) -> MockTransport {
+        let transport = MockTransport::new();
+        transport.set_responses("getTxByHash", txs);
+        transport
+    }
+
+    fn wait(transport: MockTransport, level: ConfirmationLevel) -> Result<Confirmation> {
+        wait_until(transport, level, head_block_time() + 1)
+    }
+
+    fn wait_until(
+        transport: MockTransport,
+        level: ConfirmationLevel,
+        expiration: i64,
+    ) -> Result<Confirmation> {
+        let client = IostClient::with_transport("http://127.0.0.1:30001", transport);
+        let config = TrackerConfig {
+            level,
+            ..TrackerConfig::default()
+        };
+        block_on(TxTracker::new(&client, config).wait("abc", Some(expiration)))
+    }
+
+    #[test]
+    fn should_wait_for_irreversible() {
+        let transport = node(vec![
+            not_found(),
+            tx("PENDING", "SUCCESS"),
+            tx("PACKED", "SUCCESS"),
+            tx("IRREVERSIBLE", "SUCCESS"),
+        ]);
+        let confirmation = wait(transport, ConfirmationLevel::Irreversible).unwrap();
+        assert_eq!(confirmation.status, Status::IRREVERSIBLE);
+        assert_eq!(confirmation.block_number, 1264);
+        assert_eq!(confirmation.receipt.tx_hash, "abc");
+    }
+
+    #[test]
+    fn should_stop_at_packed() {
+        let transport = node(vec![tx("PENDING", "SUCCESS"), tx("PACKED", "SUCCESS")]);
+        let confirmation = wait(transport, ConfirmationLevel::Packed).unwrap();
+        assert_eq!(confirmation.status, Status::PACKED);
+    }
+
+    #[test]
+    fn should_report_failed_tx() {
+        let transport = node(vec![tx("PACKED", "BALANCE_NOT_ENOUGH")]);
+        match wait(transport, ConfirmationLevel::Irreversible) {
+            Err(Error::TxFailed(receipt)) => {
+                assert_eq!(receipt.status.code, StatusCode::BALANCE_NOT_ENOUGH.code())
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_report_expired_tx() {
+        let transport = node(vec![not_found()]);
+        match wait_until(
+            transport,
+            ConfirmationLevel::Irreversible,
+            head_block_time() - 1,
+        ) {
+            Err(Error::TxExpired(hash)) => assert_eq!(hash, "abc"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_time_out() {
+        let transport = node(vec![tx("PACKED", "SUCCESS")]);
+        let client = IostClient::with_transport("http://127.0.0.1:30001", transport);
+        let config = TrackerConfig {
+            timeout: Duration::from_millis(0),
+            ..TrackerConfig::default()
+        };
+        match block_on(TxTracker::new(&client, config).wait("abc", None)) {
+            Err(Error::TxConfirmTimeout(hash)) => assert_eq!(hash, "abc"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(client.transport().sleeps().is_empty());
+    }
+
+    #[test]
+    fn should_wait_for_deferred_tx() {
+        let time = head_block_time();
+        let mut delayed = Tx::new(
+            time,
+            time + 1000,
+            1024,
+            alloc::vec![crate::IostAction::cancel_delay_tx(&crate::TxHash([1; 32]))],
+        );
+        delayed.delay = 500;
+        let transport = node(vec![not_found(), tx("PACKED", "SUCCESS")]);
+        let client = IostClient::with_transport("http://127.0.0.1:30001", transport);
+        let config = TrackerConfig {
+            level: ConfirmationLevel::Packed,
+            ..TrackerConfig::default()
+        };
+        let confirmation =
+            block_on(TxTracker::new(&client, config).wait_deferred(&delayed)).unwrap();
+        let deferred_hash = delayed.deferred_hash().unwrap().to_string();
+        assert_eq!(confirmation.hash, deferred_hash);
+        let path = alloc::format!("getTxByHash/{}", deferred_hash);
+        assert_eq!(client.transport().count(&path), 2);
+
+        delayed.delay = 0;
+        let config = TrackerConfig::default();
+        assert!(matches!(
+            block_on(TxTracker::new(&client, config).wait_deferred(&delayed)),
+            Err(Error::InvalidTx(_))
+        ));
+    }
+}
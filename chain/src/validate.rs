@@ -0,0 +1,267 @@
+//! Checking a transaction against the rules a node applies before accepting it, so a malformed
+//! transaction is caught before it is published.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::time::Duration;
+
+use lite_json::{parse_json, JsonValue};
+
+use crate::estimate::MIN_GAS_LIMIT;
+use crate::tx::is_account_permission;
+use crate::tx_builder::{MAINNET_CHAIN_ID, MAX_GAS_RATIO, MIN_GAS_RATIO};
+use crate::Tx;
+
+/// chain ID of the IOST testnet
+pub const TESTNET_CHAIN_ID: u32 = 1023;
+
+/// The rules of the network a transaction is meant for.
+#[derive(Debug, Clone)]
+pub struct ChainParams {
+    pub chain_id: u32,
+    pub min_gas_ratio: f64,
+    pub max_gas_ratio: f64,
+    pub min_gas_limit: f64,
+    /// longest time between a transaction's time and its expiration
+    pub max_expiration: Duration,
+}
+
+impl ChainParams {
+    /// Rules of the network `chain_id`, with the limits of the mainnet.
+    pub fn new(chain_id: u32) -> Self {
+        ChainParams {
+            chain_id,
+            min_gas_ratio: MIN_GAS_RATIO,
+            max_gas_ratio: MAX_GAS_RATIO,
+            min_gas_limit: MIN_GAS_LIMIT,
+            max_expiration: Duration::from_secs(90),
+        }
+    }
+
+    pub fn mainnet() -> Self {
+        Self::new(MAINNET_CHAIN_ID)
+    }
+
+    pub fn testnet() -> Self {
+        Self::new(TESTNET_CHAIN_ID)
+    }
+}
+
+impl Default for ChainParams {
+    fn default() -> Self {
+        Self::mainnet()
+    }
+}
+
+/// A rule a transaction breaks, see [`Tx::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// chain ID of the transaction, and of the network
+    ChainId(u32, u32),
+    /// expiration at or before the time, both in nanoseconds
+    Expired(i64, i64),
+    /// expiration further after the time than allowed, in nanoseconds
+    ExpirationTooFar(i64, Duration),
+    GasRatio(f64),
+    GasLimit(f64),
+    NoActions(),
+    NoPublisher(),
+    NoPublisherSignature(),
+    /// signer that isn't `account@permission`
+    Signer(String),
+    /// token and value of an amount limit that isn't `*` or a token symbol, with `unlimited`
+    /// or a non-negative amount
+    AmountLimit(String, String),
+    /// index of an action whose data isn't a JSON array
+    ActionData(usize),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::ChainId(actual, expected) => {
+                write!(f, "chain id {} is not {}", actual, expected)
+            }
+            Violation::Expired(time, expiration) => {
+                write!(f, "expiration {} is not after time {}", expiration, time)
+            }
+            Violation::ExpirationTooFar(window, max) => write!(
+                f,
+                "expiration is {}ns after time, more than {:?}",
+                window, max
+            ),
+            Violation::GasRatio(ratio) => write!(f, "gas ratio {} is out of bounds", ratio),
+            Violation::GasLimit(limit) => write!(f, "gas limit {} is out of bounds", limit),
+            Violation::NoActions() => write!(f, "no actions"),
+            Violation::NoPublisher() => write!(f, "no publisher"),
+            Violation::NoPublisherSignature() => write!(f, "no publisher signature"),
+            Violation::Signer(signer) => write!(f, "signer {} is not account@permission", signer),
+            Violation::AmountLimit(token, value) => {
+                write!(f, "amount limit {}: {} is invalid", token, value)
+            }
+            Violation::ActionData(index) => {
+                write!(f, "data of action {} is not a JSON array", index)
+            }
+        }
+    }
+}
+
+impl Tx {
+    /// Check the transaction against `params` and the rules every node applies, returning
+    /// every rule it breaks; an empty list means a node will accept its form. Whether the
+    /// signatures are valid is checked by [`Tx::verify`].
+    pub fn validate(&self, params: &ChainParams) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        if self.chain_id != params.chain_id {
+            violations.push(Violation::ChainId(self.chain_id, params.chain_id));
+        }
+        let window = self.expiration.saturating_sub(self.time);
+        if window <= 0 {
+            violations.push(Violation::Expired(self.time, self.expiration));
+        } else if window as u128 > params.max_expiration.as_nanos() {
+            violations.push(Violation::ExpirationTooFar(window, params.max_expiration));
+        }
+        if !(params.min_gas_ratio..=params.max_gas_ratio).contains(&self.gas_ratio) {
+            violations.push(Violation::GasRatio(self.gas_ratio));
+        }
+        if !(params.min_gas_limit..).contains(&self.gas_limit) {
+            violations.push(Violation::GasLimit(self.gas_limit));
+        }
+        if self.actions.is_empty() {
+            violations.push(Violation::NoActions());
+        }
+        if self.publisher.is_empty() {
+            violations.push(Violation::NoPublisher());
+        }
+        if self.publisher_sigs.is_empty() {
+            violations.push(Violation::NoPublisherSignature());
+        }
+        for signer in &self.signers {
+            if !is_account_permission(signer) {
+                violations.push(Violation::Signer(signer.clone()));
+            }
+        }
+        for limit in &self.amount_limit {
+            if !is_token(&limit.token) || !is_amount(&limit.value) {
+                violations.push(Violation::AmountLimit(
+                    limit.token.clone(),
+                    limit.value.clone(),
+                ));
+            }
+        }
+        for (index, action) in self.actions.iter().enumerate() {
+            let data = core::str::from_utf8(&action.data)
+                .ok()
+                .and_then(|data| parse_json(data).ok());
+            if !matches!(data, Some(JsonValue::Array(_))) {
+                violations.push(Violation::ActionData(index));
+            }
+        }
+        violations
+    }
+}
+
+/// `*` for every token, or a token symbol: 2 to 16 lowercase letters, digits or underscores.
+pub(crate) fn is_token(token: &str) -> bool {
+    token == "*"
+        || (2..=16).contains(&token.len())
+            && token
+                .bytes()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'_')
+}
+
+/// `unlimited`, or a non-negative decimal amount.
+pub(crate) fn is_amount(value: &str) -> bool {
+    if value == "unlimited" {
+        return true;
+    }
+    let mut parts = value.splitn(2, '.');
+    let integer = parts.next().unwrap_or_default();
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|c| c.is_ascii_digit());
+    match parts.next() {
+        Some(fraction) => digits(integer) && digits(fraction),
+        None => digits(integer),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AmountLimit, IostAction};
+    use alloc::string::ToString;
+    use alloc::vec;
+    use keys::algorithm;
+
+    fn signed() -> Tx {
+        let sec_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
+        let mut tx = Tx::builder()
+            .action(IostAction::transfer("alice", "bob", "10", "").unwrap())
+            .signer("bob@active")
+            .amount_limit("iost", "10.5")
+            .time(1_000)
+            .build()
+            .unwrap();
+        tx.sign("alice".to_string(), algorithm::ED25519, &sec_key)
+            .unwrap();
+        tx
+    }
+
+    #[test]
+    fn should_accept_valid_tx() {
+        assert_eq!(signed().validate(&ChainParams::mainnet()), vec![]);
+    }
+
+    #[test]
+    fn should_report_every_violation() {
+        let mut tx = signed();
+        tx.publisher_sigs.clear();
+        tx.expiration = tx.time + 91_000_000_000;
+        tx.gas_ratio = 0.5;
+        tx.gas_limit = 100.0;
+        tx.signers.push("bob".to_string());
+        tx.amount_limit.push(AmountLimit {
+            token: "IOST".to_string(),
+            value: "1".to_string(),
+        });
+        tx.amount_limit.push(AmountLimit {
+            token: "iost".to_string(),
+            value: "-1".to_string(),
+        });
+        tx.actions.push(IostAction::new(
+            "token.iost".to_string(),
+            "transfer".to_string(),
+            r#"{"to": "bob"}"#.to_string(),
+        ));
+        assert_eq!(
+            tx.validate(&ChainParams::testnet()),
+            vec![
+                Violation::ChainId(MAINNET_CHAIN_ID, TESTNET_CHAIN_ID),
+                Violation::ExpirationTooFar(91_000_000_000, Duration::from_secs(90)),
+                Violation::GasRatio(0.5),
+                Violation::GasLimit(100.0),
+                Violation::NoPublisherSignature(),
+                Violation::Signer("bob".to_string()),
+                Violation::AmountLimit("IOST".to_string(), "1".to_string()),
+                Violation::AmountLimit("iost".to_string(), "-1".to_string()),
+                Violation::ActionData(1),
+            ]
+        );
+
+        let tx = Tx {
+            chain_id: MAINNET_CHAIN_ID,
+            ..Tx::default()
+        };
+        assert_eq!(
+            tx.validate(&ChainParams::mainnet()),
+            vec![
+                Violation::Expired(0, 0),
+                Violation::GasRatio(0.0),
+                Violation::GasLimit(0.0),
+                Violation::NoActions(),
+                Violation::NoPublisher(),
+                Violation::NoPublisherSignature(),
+            ]
+        );
+    }
+}
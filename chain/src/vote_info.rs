@@ -1,8 +1,11 @@
 use alloc::string::String;
 
+use lite_json::JsonValue;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::json::{JsonObject, NoStdDeserialize};
+
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct VoteInfo {
@@ -13,3 +16,14 @@ pub struct VoteInfo {
     /// number of votes cleared
     pub cleared_votes: String,
 }
+
+impl NoStdDeserialize for VoteInfo {
+    fn no_std_deserialize(value: &JsonValue) -> crate::Result<Self> {
+        let object = JsonObject::new(value)?;
+        Ok(VoteInfo {
+            option: object.field("option")?,
+            votes: object.field("votes")?,
+            cleared_votes: object.field("cleared_votes")?,
+        })
+    }
+}
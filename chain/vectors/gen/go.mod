@@ -0,0 +1,3 @@
+module github.com/iost-official/rust-iost/chain/vectors/gen
+
+go 1.13
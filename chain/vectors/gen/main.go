@@ -0,0 +1,293 @@
+// Command gen prints the conformance vectors of iost-chain, computed by go-iost itself:
+//
+//	go mod tidy && go run . > ../go-iost.json
+//
+// Each transaction is signed with fixed keys, so the ed25519 signatures are reproducible.
+// The ed25519 transfer signed by the publisher alone is the transaction signing example of the
+// IOST documentation, and has to give its signature.
+package main
+
+import (
+	"encoding/base64"
+	"encoding/json"
+	"fmt"
+	"os"
+
+	"github.com/iost-official/go-iost/v3/account"
+	"github.com/iost-official/go-iost/v3/common"
+	"github.com/iost-official/go-iost/v3/core/block"
+	"github.com/iost-official/go-iost/v3/core/contract"
+	"github.com/iost-official/go-iost/v3/core/tx"
+	"github.com/iost-official/go-iost/v3/crypto"
+)
+
+// the ed25519 key of the IOST documentation and the signature it gives its example, and an
+// arbitrary secp256k1 key
+const (
+	ed25519Key          = "gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg=="
+	documentedSignature = "/K1HM0OEbfJ4+D3BmalpLmb03WS7BeCz4nVHBNbDrx3/A31aN2RJNxyEKhv+VSoWctfevDNRnL1kadRVxSt8CA=="
+	secp256k1Key        = "BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc="
+)
+
+// secp256k1 keys of the signers, one each, since iost-chain rejects a key signing for two
+var signerKeys = []string{
+	"CAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAg=",
+	"CQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQk=",
+}
+
+type signature struct {
+	Algorithm string `json:"algorithm"`
+	PublicKey string `json:"public_key"`
+	Signature string `json:"signature"`
+}
+
+type action struct {
+	Contract   string `json:"contract"`
+	ActionName string `json:"action_name"`
+	Data       string `json:"data"`
+}
+
+type amountLimit struct {
+	Token string `json:"token"`
+	Value string `json:"value"`
+}
+
+// transaction is the JSON of a transaction as iost-chain reads it.
+type transaction struct {
+	Time          int64         `json:"time"`
+	Expiration    int64         `json:"expiration"`
+	GasRatio      float64       `json:"gas_ratio"`
+	GasLimit      float64       `json:"gas_limit"`
+	Delay         int64         `json:"delay"`
+	ChainID       uint32        `json:"chain_id"`
+	Signers       []string      `json:"signers"`
+	Actions       []action      `json:"actions"`
+	AmountLimit   []amountLimit `json:"amount_limit"`
+	Signatures    []signature   `json:"signatures"`
+	Publisher     string        `json:"publisher"`
+	PublisherSigs []signature   `json:"publisher_sigs"`
+}
+
+type docVector struct {
+	Name   string      `json:"name"`
+	Source string      `json:"source"`
+	SecKey string      `json:"sec_key"`
+	Tx     transaction `json:"tx"`
+}
+
+type txVector struct {
+	Name         string      `json:"name"`
+	SecKey       string      `json:"sec_key"`
+	SignerKeys   []string    `json:"signer_keys"`
+	Tx           transaction `json:"tx"`
+	ReferredTx   string      `json:"referred_tx"`
+	Bytes        string      `json:"bytes"`
+	SignedBytes  string      `json:"signed_bytes"`
+	Digest       string      `json:"digest"`
+	SignedDigest string      `json:"signed_digest"`
+	Hash         string      `json:"hash"`
+}
+
+// byteArray marshals as an array of numbers, like serde reads a Vec<u8>.
+type byteArray []byte
+
+func (b byteArray) MarshalJSON() ([]byte, error) {
+	numbers := make([]int, len(b))
+	for i, v := range b {
+		numbers[i] = int(v)
+	}
+	return json.Marshal(numbers)
+}
+
+type head struct {
+	Version             int64     `json:"version"`
+	ParentHash          byteArray `json:"parent_hash"`
+	TxMerkleHash        byteArray `json:"tx_merkle_hash"`
+	TxReceiptMerkleHash byteArray `json:"tx_receipt_merkle_hash"`
+	Info                byteArray `json:"info"`
+	Number              int64     `json:"number"`
+	Witness             string    `json:"witness"`
+	Time                int64     `json:"time"`
+}
+
+type headVector struct {
+	Name  string `json:"name"`
+	Head  head   `json:"head"`
+	Bytes string `json:"bytes"`
+	Hash  string `json:"hash"`
+}
+
+type vectors struct {
+	Version      int          `json:"version"`
+	Notes        []string     `json:"notes"`
+	Documented   []docVector  `json:"documented"`
+	Transactions []txVector   `json:"transactions"`
+	Heads        []headVector `json:"heads"`
+}
+
+func keyPair(key string, algorithm crypto.Algorithm) *account.KeyPair {
+	secKey, err := base64.StdEncoding.DecodeString(key)
+	check(err)
+	kp, err := account.NewKeyPair(secKey, algorithm)
+	check(err)
+	return kp
+}
+
+func check(err error) {
+	if err != nil {
+		fmt.Fprintln(os.Stderr, err)
+		os.Exit(1)
+	}
+}
+
+func encode(b []byte) string {
+	return base64.StdEncoding.EncodeToString(b)
+}
+
+func signatures(signs []*crypto.Signature) []signature {
+	out := []signature{}
+	for _, s := range signs {
+		name := "ED25519"
+		if s.Algorithm == crypto.Secp256k1 {
+			name = "SECP256K1"
+		}
+		out = append(out, signature{name, encode(s.Pubkey), encode(s.Sig)})
+	}
+	return out
+}
+
+func transfer(from, to, amount string) *tx.Action {
+	data := fmt.Sprintf(`["iost", "%s", "%s", "%s", ""]`, from, to, amount)
+	return tx.NewAction("token.iost", "transfer", data)
+}
+
+// newTx is a transaction at a fixed time, with gas ratio 1 and gas limit 500,000.
+func newTx(actions []*tx.Action, signers []string, limits []*contract.Amount, delay int64) *tx.Tx {
+	t := tx.NewTx(actions, signers, 50000000, 100, 1544709692318715000, delay, 1024)
+	t.Time = 1544709662543340000
+	t.AmountLimit = limits
+	return t
+}
+
+// vector signs t for each signer with its key of signerKeys, then as publisher with key and
+// algorithm, and records what go-iost makes of it.
+func vector(name string, t *tx.Tx, key string, algorithm crypto.Algorithm, publisher string) txVector {
+	keys := signerKeys[:len(t.Signers)]
+	for i, signer := range t.Signers {
+		sig, err := tx.SignTxContent(t, signer, keyPair(keys[i], crypto.Secp256k1))
+		check(err)
+		t.Signs = append(t.Signs, sig)
+	}
+	t.Publisher = publisher
+	kp := keyPair(key, algorithm)
+	t.PublishSigns = []*crypto.Signature{kp.Sign(common.Sha3(t.ToBytes(tx.Publish)))}
+	return record(name, t, key, keys)
+}
+
+func record(name string, t *tx.Tx, key string, keys []string) txVector {
+	actions := []action{}
+	for _, a := range t.Actions {
+		actions = append(actions, action{a.Contract, a.ActionName, a.Data})
+	}
+	limits := []amountLimit{}
+	for _, l := range t.AmountLimit {
+		limits = append(limits, amountLimit{l.Token, l.Val})
+	}
+	signers := t.Signers
+	if signers == nil {
+		signers = []string{}
+	}
+	base := t.ToBytes(tx.Base)
+	publish := t.ToBytes(tx.Publish)
+	return txVector{
+		Name:       name,
+		SecKey:     key,
+		SignerKeys: append([]string{}, keys...),
+		Tx: transaction{
+			Time:          t.Time,
+			Expiration:    t.Expiration,
+			GasRatio:      float64(t.GasRatio) / 100,
+			GasLimit:      float64(t.GasLimit) / 100,
+			Delay:         t.Delay,
+			ChainID:       t.ChainID,
+			Signers:       signers,
+			Actions:       actions,
+			AmountLimit:   limits,
+			Signatures:    signatures(t.Signs),
+			Publisher:     t.Publisher,
+			PublisherSigs: signatures(t.PublishSigns),
+		},
+		ReferredTx:   encode(t.ReferredTx),
+		Bytes:        encode(base),
+		SignedBytes:  encode(publish),
+		Digest:       encode(common.Sha3(base)),
+		SignedDigest: encode(common.Sha3(publish)),
+		Hash:         common.Base58Encode(t.Hash()),
+	}
+}
+
+func headVec(name string, info []byte) headVector {
+	h := &block.BlockHead{
+		Version:             1,
+		ParentHash:          common.Sha3([]byte("parent")),
+		TxMerkleHash:        common.Sha3([]byte("txs")),
+		TxReceiptMerkleHash: common.Sha3([]byte("receipts")),
+		Info:                info,
+		Number:              10,
+		Witness:             "Gcv8c2tH8qZrUYnKdEEdTtASsxivic2834MQW6mgxqto",
+		Time:                1544709662500000000,
+	}
+	return headVector{
+		Name: name,
+		Head: head{h.Version, h.ParentHash, h.TxMerkleHash, h.TxReceiptMerkleHash, h.Info,
+			h.Number, h.Witness, h.Time},
+		Bytes: encode(h.ToBytes()),
+		Hash:  common.Base58Encode(h.Hash()),
+	}
+}
+
+func main() {
+	unlimited := []*contract.Amount{{Token: "*", Val: "unlimited"}}
+	limits := []*contract.Amount{{Token: "iost", Val: "100"}, {Token: "ram", Val: "1.5"}}
+	one := []*tx.Action{tx.NewAction("token.iost", "transfer",
+		`["iost", "testaccount", "anothertest", "100", "this is an example transfer"]`)}
+	two := []*tx.Action{transfer("alice", "bob", "1"), transfer("alice", "carol", "2")}
+
+	documented := vector("ed25519 publisher, no signers", newTx(one, nil, unlimited, 0), ed25519Key, crypto.Ed25519, "testaccount")
+	if documented.Tx.PublisherSigs[0].Signature != documentedSignature {
+		check(fmt.Errorf("go-iost signs the documented example as %s", documented.Tx.PublisherSigs[0].Signature))
+	}
+	delayed := newTx(two, []string{"bob@active"}, limits, 60*1000000000)
+	delayedVector := vector("ed25519, one signer, delayed", delayed, ed25519Key, crypto.Ed25519, "alice")
+
+	out := vectors{
+		Version: 2,
+		Notes: []string{
+			"Generated by vectors/gen with go-iost: cd chain/vectors/gen && go mod tidy && go run . > ../go-iost.json. Do not edit by hand.",
+			"documented holds the transaction signing example of the IOST documentation, which is checked by verifying and re-signing it.",
+			"Every field of a transaction vector is required, all base64 unless noted: sec_key (the key of the publisher signature), signer_keys (the key of each signer signature), bytes (Tx::customized_to_serialize_data(false)), signed_bytes (with true), digest and signed_digest (their SHA3-256), hash (base58), and referred_tx for deferred transactions. A head carries head, bytes and hash (base58).",
+		},
+		Documented: []docVector{{
+			Name:   "ed25519 publisher signature, no signers",
+			Source: "IOST documentation, transaction signing example",
+			SecKey: ed25519Key,
+			Tx:     documented.Tx,
+		}},
+		Transactions: []txVector{
+			documented,
+			vector("secp256k1 publisher, two actions, no amount limits", newTx(two, nil, nil, 0), secp256k1Key, crypto.Secp256k1, "alice"),
+			vector("ed25519, two signers, two amount limits", newTx(one, []string{"bob@active", "carol@owner"}, limits, 0), ed25519Key, crypto.Ed25519, "testaccount"),
+			vector("secp256k1, one signer, no actions", newTx(nil, []string{"bob@active"}, unlimited, 0), secp256k1Key, crypto.Secp256k1, "alice"),
+			delayedVector,
+			// go-iost decides what the deferred transaction carries
+			record("deferred from the delayed transaction", delayed.DeferTx(), "", nil),
+		},
+		Heads: []headVector{
+			headVec("head without info", nil),
+			headVec("head with info", []byte(`{"mode":0,"thread":0,"batch_index":null}`)),
+		},
+	}
+	encoder := json.NewEncoder(os.Stdout)
+	encoder.SetIndent("", "  ")
+	check(encoder.Encode(out))
+}
@@ -26,6 +26,11 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 let field_reads = fields.named.iter().map(|f| {
                     let ident = &f.ident;
                     let ty = &f.ty;
+                    if crate::is_skipped(f) {
+                        return quote_spanned! {f.span() =>
+                            let #ident = <#ty as Default>::default();
+                        };
+                    }
                     quote_spanned! {f.span() =>
                         let #ident = <#ty as #root::Read>::read(bytes, pos)?;
                     }
@@ -48,6 +53,11 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 let field_reads = fields.unnamed.iter().enumerate().map(|(i, f)| {
                     let ty = &f.ty;
                     let ident = Ident::new(format!("field_{}", i).as_str(), call_site);
+                    if crate::is_skipped(f) {
+                        return quote_spanned! {f.span() =>
+                            let #ident = <#ty as Default>::default();
+                        };
+                    }
                     quote_spanned! {f.span() =>
                         let #ident = <#ty as #root::Read>::read(bytes, pos)?;
                     }
@@ -24,29 +24,38 @@ pub fn expand(input: TokenStream) -> TokenStream {
     let writes = match input.data {
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => {
-                let recurse = fields.named.iter().map(|f| {
-                    let name = &f.ident;
-                    let access = quote_spanned!(call_site => #var.#name);
-                    quote_spanned! { f.span() =>
-                        #root::Write::write(&#access, bytes, pos)?;
-                    }
-                });
+                let recurse = fields
+                    .named
+                    .iter()
+                    .filter(|f| !crate::is_skipped(f))
+                    .map(|f| {
+                        let name = &f.ident;
+                        let access = quote_spanned!(call_site => #var.#name);
+                        quote_spanned! { f.span() =>
+                            #root::Write::write(&#access, bytes, pos)?;
+                        }
+                    });
                 quote! {
                     #(#recurse)*
                     Ok(())
                 }
             }
             Fields::Unnamed(ref fields) => {
-                let recurse = fields.unnamed.iter().enumerate().map(|(i, f)| {
-                    let index = Index {
-                        index: i as u32,
-                        span: call_site,
-                    };
-                    let access = quote_spanned!(call_site => #var.#index);
-                    quote_spanned! { f.span() =>
-                        #root::Write::write(&#access, bytes, pos)?;
-                    }
-                });
+                let recurse = fields
+                    .unnamed
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, f)| !crate::is_skipped(f))
+                    .map(|(i, f)| {
+                        let index = Index {
+                            index: i as u32,
+                            span: call_site,
+                        };
+                        let access = quote_spanned!(call_site => #var.#index);
+                        quote_spanned! { f.span() =>
+                            #root::Write::write(&#access, bytes, pos)?;
+                        }
+                    });
                 quote! {
                     #(#recurse)*
                     Ok(())
@@ -10,7 +10,7 @@ mod derive_serialize_data;
 
 use crate::proc_macro::TokenStream;
 use proc_macro2::Span;
-use syn::{DeriveInput, Lit, LitStr, Meta, Path};
+use syn::{DeriveInput, Field, Lit, LitStr, Meta, Path};
 
 /// Derive the `Digest` trait
 // #[inline]
@@ -28,21 +28,21 @@ pub fn derive_serialize_data(input: TokenStream) -> TokenStream {
 
 /// Derive the `Write` trait
 #[inline]
-#[proc_macro_derive(Write, attributes(iost_root_path))]
+#[proc_macro_derive(Write, attributes(iost_root_path, iost_skip))]
 pub fn derive_write(input: TokenStream) -> TokenStream {
     crate::derive_write::expand(input)
 }
 
 /// Derive the `Read` trait
 #[inline]
-#[proc_macro_derive(Read, attributes(iost_root_path))]
+#[proc_macro_derive(Read, attributes(iost_root_path, iost_skip))]
 pub fn derive_read(input: TokenStream) -> TokenStream {
     crate::derive_read::expand(input)
 }
 
 /// Derive the `NumberBytes` trait
 #[inline]
-#[proc_macro_derive(NumberBytes, attributes(iost_root_path))]
+#[proc_macro_derive(NumberBytes, attributes(iost_root_path, iost_skip))]
 pub fn derive_num_bytes(input: TokenStream) -> TokenStream {
     crate::derive_num_bytes::expand(input)
 }
@@ -65,6 +65,15 @@ const DEFAULT_ROOT_PATH: &str = "::eosio";
 #[cfg(not(feature = "internal-use-only-root-path-is-eosio"))]
 const DEFAULT_ROOT_PATH: &str = "::eosio_core";
 
+/// Whether a field is marked `#[iost_skip]`: left out of the binary layout, and read as its
+/// default.
+pub(crate) fn is_skipped(field: &Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("iost_skip"))
+}
+
 /// Get the root path for types/traits.
 pub(crate) fn root_path(input: &DeriveInput) -> Path {
     let litstr = input
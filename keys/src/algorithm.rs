@@ -1,4 +1,5 @@
 use alloc::boxed::Box;
+use alloc::string::ToString;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
 
@@ -6,6 +7,7 @@ use ed25519_dalek::{Signature, Signer, Verifier};
 #[cfg(feature = "std")]
 use rand::thread_rng;
 
+use crate::error::Error;
 use crate::Result;
 
 pub const ED25519: &str = "ED25519";
@@ -26,11 +28,26 @@ pub trait Algorithm {
 pub struct AlgorithmSecp256k1;
 pub struct AlgorithmEd25519;
 
+/// `ED25519` or `SECP256K1` for `algorithm_name` in any case, `None` for other algorithms.
+pub fn normalize(algorithm_name: &str) -> Option<&'static str> {
+    [ED25519, SECP256K1]
+        .iter()
+        .copied()
+        .find(|name| name.eq_ignore_ascii_case(algorithm_name))
+}
+
+/// The algorithm named `algorithm_name`, falling back to secp256k1 for names it doesn't know.
+/// Use [`try_new`] to reject them instead.
 pub fn new(algorithm_name: &str) -> Box<dyn Algorithm> {
-    match algorithm_name {
-        ED25519 => Box::new(AlgorithmEd25519),
-        SECP256K1 => Box::new(AlgorithmSecp256k1),
-        _ => Box::new(AlgorithmSecp256k1),
+    try_new(algorithm_name).unwrap_or_else(|_| Box::new(AlgorithmSecp256k1))
+}
+
+/// The algorithm named `algorithm_name`, in any case.
+pub fn try_new(algorithm_name: &str) -> Result<Box<dyn Algorithm>> {
+    match normalize(algorithm_name) {
+        Some(ED25519) => Ok(Box::new(AlgorithmEd25519)),
+        Some(_) => Ok(Box::new(AlgorithmSecp256k1)),
+        None => Err(Error::UnsupportedAlgorithm(algorithm_name.to_string())),
     }
 }
 
@@ -42,8 +59,13 @@ impl Algorithm for AlgorithmEd25519 {
     }
 
     fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> bool {
-        let public_key = ed25519_dalek::PublicKey::from_bytes(pub_key).unwrap();
-        let sig = Signature::try_from(signature).unwrap();
+        let (public_key, sig) = match (
+            ed25519_dalek::PublicKey::from_bytes(pub_key),
+            Signature::try_from(signature),
+        ) {
+            (Ok(public_key), Ok(sig)) => (public_key, sig),
+            _ => return false,
+        };
         public_key.verify(message, &sig).is_ok()
     }
 
@@ -56,7 +78,8 @@ impl Algorithm for AlgorithmEd25519 {
     }
 
     fn get_pub_key(&self, sec_key: &[u8]) -> crate::Result<Vec<u8>> {
-        let key_pair = ed25519_dalek::Keypair::from_bytes(sec_key).unwrap();
+        let key_pair = ed25519_dalek::Keypair::from_bytes(sec_key)
+            .map_err(|_| crate::error::Error::ErrorEd25519)?;
         Ok(Vec::from(key_pair.public.as_ref()))
     }
 
@@ -75,10 +98,14 @@ impl Algorithm for AlgorithmSecp256k1 {
     }
 
     fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> bool {
-        let msg = secp256k1::Message::parse_slice(message).unwrap();
-        let sig = secp256k1::Signature::parse_slice(signature).unwrap();
-        let public_key = secp256k1::PublicKey::parse_slice(pub_key, None).unwrap();
-        secp256k1::verify(&msg, &sig, &public_key)
+        match (
+            secp256k1::Message::parse_slice(message),
+            secp256k1::Signature::parse_slice(signature),
+            secp256k1::PublicKey::parse_slice(pub_key, None),
+        ) {
+            (Ok(msg), Ok(sig), Ok(public_key)) => secp256k1::verify(&msg, &sig, &public_key),
+            _ => false,
+        }
     }
 
     #[cfg(feature = "std")]
@@ -89,7 +116,7 @@ impl Algorithm for AlgorithmSecp256k1 {
     }
 
     fn get_pub_key(&self, sec_key: &[u8]) -> Result<Vec<u8>> {
-        let secret_key = secp256k1::SecretKey::parse_slice(sec_key).unwrap();
+        let secret_key = secp256k1::SecretKey::parse_slice(sec_key)?;
         let public_key = secp256k1::PublicKey::from_secret_key(&secret_key);
         Ok(public_key.serialize_compressed().to_vec())
     }
@@ -138,4 +165,26 @@ mod test {
             result
         );
     }
+
+    #[test]
+    fn should_reject_unknown_algorithms() {
+        assert_eq!(normalize("ed25519"), Some(ED25519));
+        assert_eq!(normalize("Secp256k1"), Some(SECP256K1));
+        assert_eq!(normalize("rsa"), None);
+        let ed25519 = try_new("ed25519").unwrap();
+        assert_eq!(
+            ed25519.get_pub_key(&[7u8; 32]).err(),
+            Some(Error::ErrorEd25519)
+        );
+        assert_eq!(
+            try_new("rsa").err().map(|err| err.to_string()),
+            Some("unsupported algorithm rsa".to_string())
+        );
+        // `new` keeps falling back to secp256k1
+        let key = [7u8; 32];
+        assert_eq!(
+            super::new("rsa").get_pub_key(&key).unwrap(),
+            AlgorithmSecp256k1.get_pub_key(&key).unwrap()
+        );
+    }
 }
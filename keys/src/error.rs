@@ -1,4 +1,4 @@
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use core::fmt;
 
 use crate::base58;
@@ -19,6 +19,8 @@ pub enum Error {
     Hash(bitcoin_hashes::error::Error),
     /// verify failed
     VerifyFailed,
+    /// algorithm other than ED25519 and SECP256K1
+    UnsupportedAlgorithm(String),
 }
 
 impl fmt::Display for Error {
@@ -30,6 +32,7 @@ impl fmt::Display for Error {
             Error::Hash(ref e) => f.write_str(&e.to_string()),
             Error::VerifyFailed => f.write_str("Verify failed"),
             Error::ErrorSecp256k1 => f.write_str("Secp256k1 failed"),
+            Error::UnsupportedAlgorithm(ref name) => write!(f, "unsupported algorithm {}", name),
         }
     }
 }
@@ -0,0 +1,265 @@
+//! Canned responses for every endpoint, shaped like the answers of a real node.
+
+use serde_json::{json, Value};
+
+pub const CHAIN_ID: i32 = 1024;
+
+pub fn block_hash(number: i64) -> String {
+    format!("MockBlock{}", number)
+}
+
+/// Block number encoded in a hash made by [`block_hash`].
+pub fn block_number(hash: &str) -> Option<i64> {
+    hash.strip_prefix("MockBlock")?.parse().ok()
+}
+
+pub fn node_info() -> Value {
+    json!({
+        "build_time": "20200101_000000+0000",
+        "git_hash": "mock",
+        "mode": "ModeNormal",
+        "network": {"id": "12D3KooWMockNode", "peer_count": 1},
+        "code_version": "3.4.0",
+        "server_time": "1598918258274417000"
+    })
+}
+
+pub fn chain_info(head_block: i64, lib_block: i64) -> Value {
+    json!({
+        "net_name": "mocknet",
+        "protocol_version": "1.0",
+        "chain_id": CHAIN_ID,
+        "head_block": head_block.to_string(),
+        "head_block_hash": block_hash(head_block),
+        "lib_block": lib_block.to_string(),
+        "lib_block_hash": block_hash(lib_block),
+        "witness_list": ["producer00"],
+        "lib_witness_list": ["producer00"],
+        "pending_witness_list": ["producer00"],
+        "head_block_time": "1598918258274417000",
+        "lib_block_time": "1598918258274417000"
+    })
+}
+
+pub fn gas_ratio() -> Value {
+    json!({"lowest_gas_ratio": 1, "median_gas_ratio": 1})
+}
+
+pub fn ram_info() -> Value {
+    json!({
+        "available_ram": "100000000000",
+        "used_ram": "1000000",
+        "total_ram": "100001000000",
+        "buy_price": 0.03,
+        "sell_price": 0.03
+    })
+}
+
+pub fn transaction(hash: &str) -> Value {
+    json!({
+        "hash": hash,
+        "time": "1598918258274417000",
+        "expiration": "1598918348274417000",
+        "gas_ratio": 1,
+        "gas_limit": 1000000,
+        "delay": "0",
+        "chain_id": CHAIN_ID,
+        "actions": [{
+            "contract": "token.iost",
+            "action_name": "transfer",
+            "data": "[\"iost\",\"admin\",\"lispczz3\",\"100\",\"\"]"
+        }],
+        "signers": [],
+        "publisher": "admin",
+        "referred_tx": "",
+        "amount_limit": [{"token": "*", "value": "unlimited"}],
+        "tx_receipt": tx_receipt(hash)
+    })
+}
+
+pub fn tx_receipt(hash: &str) -> Value {
+    json!({
+        "tx_hash": hash,
+        "gas_usage": 2577,
+        "ram_usage": {},
+        "status_code": "SUCCESS",
+        "message": "",
+        "returns": ["[]"],
+        "receipts": [{
+            "func_name": "token.iost/transfer",
+            "content": "[\"iost\",\"admin\",\"lispczz3\",\"100\"]"
+        }]
+    })
+}
+
+pub fn tx_by_hash(hash: &str, block_number: i64, status: &str) -> Value {
+    json!({
+        "status": status,
+        "transaction": transaction(hash),
+        "block_number": block_number.to_string()
+    })
+}
+
+pub fn block(number: i64, complete: bool, status: &str) -> Value {
+    let transactions = if complete {
+        json!([transaction(&format!("MockTx{}", number))])
+    } else {
+        json!([])
+    };
+    json!({
+        "status": status,
+        "block": {
+            "hash": block_hash(number),
+            "version": "1",
+            "parent_hash": block_hash(number - 1),
+            "tx_merkle_hash": "",
+            "tx_receipt_merkle_hash": "",
+            "number": number.to_string(),
+            "witness": "producer00",
+            "time": "1598918258274417000",
+            "gas_usage": 2577,
+            "tx_count": "1",
+            "info": {"mode": 0, "thread": 0, "batch_index": []},
+            "transactions": transactions
+        }
+    })
+}
+
+pub fn account(name: &str) -> Value {
+    let item = json!({"id": "IOST2mCzj85xkSvMf1eoGtrexQcwE6gK8z5xr6Kc48DwxXPCqQJva4", "is_key_pair": true, "weight": "1", "permission": ""});
+    json!({
+        "name": name,
+        "balance": 1000,
+        "gas_info": {
+            "current_total": 3000000,
+            "transferable_gas": 0,
+            "pledge_gas": 3000000,
+            "increase_speed": 11,
+            "limit": 3000000,
+            "pledged_info": [{"pledger": name, "amount": 10}]
+        },
+        "ram_info": {"available": "1000", "used": "0", "total": "1000"},
+        "permissions": {
+            "active": {"name": "active", "group_names": [], "items": [item.clone()], "threshold": "1"},
+            "owner": {"name": "owner", "group_names": [], "items": [item], "threshold": "1"}
+        },
+        "groups": {},
+        "frozen_balances": [],
+        "vote_infos": []
+    })
+}
+
+pub fn token_balance() -> Value {
+    json!({"balance": 1000, "frozen_balances": []})
+}
+
+pub fn token_info(symbol: &str) -> Value {
+    json!({
+        "symbol": symbol,
+        "full_name": symbol,
+        "issuer": "token.iost",
+        "total_supply": "90000000000",
+        "current_supply": "21000000000",
+        "total_supply_float": 90000000000.0,
+        "current_supply_float": 21000000000.0,
+        "decimal": 8,
+        "can_transfer": true,
+        "only_issuer_can_transfer": false
+    })
+}
+
+pub fn contract(id: &str) -> Value {
+    json!({
+        "id": id,
+        "code": "",
+        "language": "javascript",
+        "version": "1.0.0",
+        "abis": [{"name": "transfer", "args": ["string", "string", "string", "string", "string"], "amount_limit": []}]
+    })
+}
+
+pub fn contract_storage() -> Value {
+    json!({"data": "", "block_hash": block_hash(1), "block_number": "1"})
+}
+
+pub fn contract_storage_fields() -> Value {
+    json!({"fields": []})
+}
+
+pub fn batch_contract_storage() -> Value {
+    json!({"datas": [], "block_hash": block_hash(1), "block_number": "1"})
+}
+
+pub fn producer_vote_info() -> Value {
+    json!({
+        "pubkey": "",
+        "loc": "",
+        "url": "",
+        "net_id": "",
+        "is_producer": false,
+        "status": "APPROVED",
+        "online": true,
+        "votes": "0"
+    })
+}
+
+pub fn bonus() -> Value {
+    json!({"bonus": 0})
+}
+
+pub fn voter_bonus() -> Value {
+    json!({"bonus": 0, "detail": {}})
+}
+
+pub fn error(code: i32, message: &str) -> Value {
+    json!({"code": code, "message": message})
+}
+
+/// Status and body of the answer to `path`, e.g. `getBlockByNumber/3/true`, on a chain whose
+/// head block is `head_block` and whose last irreversible block is `lib_block`. `sendTx` and
+/// `execTx` depend on the posted transaction, so they are left to the caller.
+pub fn route(path: &str, head_block: i64, lib_block: i64) -> (u16, Value) {
+    let status = |number: i64| {
+        if number <= lib_block {
+            "IRREVERSIBLE"
+        } else {
+            "PENDING"
+        }
+    };
+    let block = |number: i64, complete: &str| {
+        if number < 0 || number > head_block {
+            (404, error(5, "block not found"))
+        } else {
+            (200, block(number, complete == "true", status(number)))
+        }
+    };
+
+    let segments: Vec<&str> = path.split('/').collect();
+    match segments.as_slice() {
+        ["getNodeInfo"] => (200, node_info()),
+        ["getChainInfo"] => (200, chain_info(head_block, lib_block)),
+        ["getGasRatio"] => (200, gas_ratio()),
+        ["getRAMInfo"] => (200, ram_info()),
+        ["getTxByHash", hash] => (200, tx_by_hash(hash, lib_block, status(lib_block))),
+        ["getTxReceiptByTxHash", hash] => (200, tx_receipt(hash)),
+        ["getBlockByHash", hash, complete] => match block_number(hash) {
+            Some(number) => block(number, complete),
+            None => (404, error(5, "block not found")),
+        },
+        ["getBlockByNumber", number, complete] => match number.parse() {
+            Ok(number) => block(number, complete),
+            Err(_) => (400, error(3, "invalid block number")),
+        },
+        ["getAccount", name, _] => (200, account(name)),
+        ["getTokenBalance", _, _, _] => (200, token_balance()),
+        ["getTokenInfo", symbol, _] => (200, token_info(symbol)),
+        ["getContract", id, _] => (200, contract(id)),
+        ["getProducerVoteInfo", _, _] => (200, producer_vote_info()),
+        ["getCandidateBonus", _, _] => (200, bonus()),
+        ["getVoterBonus", _, _] => (200, voter_bonus()),
+        ["getContractStorage"] => (200, contract_storage()),
+        ["getContractStorageFields"] => (200, contract_storage_fields()),
+        ["getBatchContractStorage"] => (200, batch_contract_storage()),
+        _ => (404, error(5, "Not Found")),
+    }
+}
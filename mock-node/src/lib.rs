@@ -0,0 +1,487 @@
+//! An in-process mock of the IOST node HTTP API.
+//!
+//! [`MockNode::start`] serves canned answers for every endpoint of `IostClient` on an
+//! ephemeral local port, so integration tests can run without a node. Answers can be
+//! replaced per endpoint, and transactions posted to `sendTx` are recorded and checked with
+//! `Tx::verify`.
+
+pub mod fixtures;
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use iost_chain::Tx;
+use serde_json::Value;
+use sha3::{Digest, Sha3_256};
+
+/// An HTTP request received by the mock node.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    /// path without the leading `/`, e.g. `getBlockByNumber/3/true`
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// the `/` separated parts of the path
+    pub fn segments(&self) -> Vec<&str> {
+        self.path.split('/').collect()
+    }
+}
+
+/// Status code and body the mock node answers with.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    pub fn json(status: u16, body: Value) -> Self {
+        MockResponse {
+            status,
+            body: body.to_string().into_bytes(),
+        }
+    }
+
+    pub fn ok(body: Value) -> Self {
+        Self::json(200, body)
+    }
+
+    /// an error body in the format of the node's gateway
+    pub fn error(status: u16, code: i32, message: &str) -> Self {
+        Self::json(status, fixtures::error(code, message))
+    }
+}
+
+/// A transaction posted to `sendTx`.
+#[derive(Debug, Clone)]
+pub struct SentTx {
+    pub body: Vec<u8>,
+    /// the decoded transaction, if the body parsed
+    pub tx: Option<Tx>,
+    /// the hash returned to the client, or why the transaction was rejected
+    pub result: Result<String, String>,
+}
+
+type Handler = Arc<dyn Fn(&Request) -> MockResponse + Send + Sync>;
+
+struct State {
+    head_block: i64,
+    lib_block: i64,
+    handlers: Vec<(String, Handler)>,
+    requests: Vec<Request>,
+    sent_txs: Vec<SentTx>,
+}
+
+/// A mock node listening on `127.0.0.1`. The server stops when the value is dropped.
+pub struct MockNode {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockNode {
+    /// Start serving on an ephemeral port, with head block 100 and LIB 90.
+    pub fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            head_block: 100,
+            lib_block: 90,
+            handlers: Vec::new(),
+            requests: Vec::new(),
+            sent_txs: Vec::new(),
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let state = state.clone();
+            let shutdown = shutdown.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let state = state.clone();
+                        thread::spawn(move || {
+                            let _ = serve(stream, &state);
+                        });
+                    }
+                }
+            })
+        };
+
+        Ok(MockNode {
+            addr,
+            state,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// base URL to pass to `IostClient::new`, e.g. `http://127.0.0.1:41234`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn set_head_block(&self, number: i64) {
+        self.state.lock().unwrap().head_block = number;
+    }
+
+    pub fn set_lib_block(&self, number: i64) {
+        self.state.lock().unwrap().lib_block = number;
+    }
+
+    /// Answer requests whose path starts with `prefix` with `handler`, instead of the
+    /// fixtures. Handlers registered later take precedence.
+    pub fn set_handler<F>(&self, prefix: &str, handler: F)
+    where
+        F: Fn(&Request) -> MockResponse + Send + Sync + 'static,
+    {
+        self.state
+            .lock()
+            .unwrap()
+            .handlers
+            .push((prefix.to_string(), Arc::new(handler)));
+    }
+
+    /// Answer requests whose path starts with `prefix` with a fixed response.
+    pub fn set_response(&self, prefix: &str, response: MockResponse) {
+        self.set_handler(prefix, move |_| response.clone());
+    }
+
+    /// Go back to the fixtures for every endpoint.
+    pub fn clear_handlers(&self) {
+        self.state.lock().unwrap().handlers.clear();
+    }
+
+    /// every request received so far
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// every transaction posted to `sendTx` so far, accepted or not
+    pub fn sent_txs(&self) -> Vec<SentTx> {
+        self.state.lock().unwrap().sent_txs.clone()
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // wake up the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn serve(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let request = match read_request(&mut reader)? {
+        Some(request) => request,
+        None => return Ok(()),
+    };
+
+    let handler = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(request.clone());
+        state
+            .handlers
+            .iter()
+            .rev()
+            .find(|(prefix, _)| request.path.starts_with(prefix.as_str()))
+            .map(|(_, handler)| handler.clone())
+    };
+    let response = match handler {
+        Some(handler) => handler(&request),
+        None => route(&request, state),
+    };
+
+    write_response(stream, &response)
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> io::Result<Option<Request>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let path = target
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(Request { method, path, body }))
+}
+
+fn write_response(mut stream: TcpStream, response: &MockResponse) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        if response.status == 200 { "OK" } else { "Error" },
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+/// The fixture answer for a request.
+fn route(request: &Request, state: &Mutex<State>) -> MockResponse {
+    let (head_block, lib_block) = {
+        let state = state.lock().unwrap();
+        (state.head_block, state.lib_block)
+    };
+    match request.segments().as_slice() {
+        ["sendTx"] => send_tx(request, state),
+        ["execTx"] => MockResponse::ok(fixtures::tx_receipt(&placeholder_hash(&request.body))),
+        _ => {
+            let (status, body) = fixtures::route(&request.path, head_block, lib_block);
+            MockResponse::json(status, body)
+        }
+    }
+}
+
+fn send_tx(request: &Request, state: &Mutex<State>) -> MockResponse {
+    let tx: Option<Tx> = serde_json::from_slice(&request.body).ok();
+    let result = match &tx {
+        None => Err("invalid tx json".to_string()),
+        Some(tx) if tx.publisher_sigs.is_empty() => {
+            Err("tx has no publisher signature".to_string())
+        }
+        Some(tx) => match tx.verify() {
+            Ok(()) => tx
+                .hash()
+                .map(|hash| hash.to_string())
+                .map_err(|err| format!("invalid tx: {:?}", err)),
+            Err(err) => Err(format!("invalid signature: {:?}", err)),
+        },
+    };
+
+    state.lock().unwrap().sent_txs.push(SentTx {
+        body: request.body.clone(),
+        tx,
+        result: result.clone(),
+    });
+
+    match result {
+        Ok(hash) => MockResponse::ok(serde_json::json!({ "hash": hash })),
+        Err(message) => MockResponse::error(400, 3, &message),
+    }
+}
+
+/// Base58 SHA3-256 of the request body, for `execTx`, whose transactions needn't be signed.
+fn placeholder_hash(body: &[u8]) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.input(body);
+    bs58::encode(hasher.result()).into_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use iost_chain::{
+        blocking, BatchContractStoragePost, ContractStorageFieldsPost, ContractStoragePost,
+        IostAction, IostClient, NodeErrorKind, RetryPolicy, Status, TrackerConfig,
+    };
+    use keys::algorithm;
+
+    const SEC_KEY: &str =
+        "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1";
+
+    fn client(node: &MockNode) -> blocking::IostClient {
+        blocking::IostClient::new(&node.url()).with_retry_policy(RetryPolicy::none())
+    }
+
+    fn signed_tx() -> Tx {
+        let action = IostAction::transfer("admin", "lispczz3", "10", "").unwrap();
+        let mut tx = Tx::from_action(vec![action]);
+        let sec_key = bs58::decode(SEC_KEY).into_vec().unwrap();
+        tx.sign("admin".to_string(), algorithm::ED25519, &sec_key)
+            .unwrap();
+        tx
+    }
+
+    #[test]
+    fn should_serve_every_endpoint() {
+        let node = MockNode::start().unwrap();
+        let client = client(&node);
+
+        let chain_info = client.get_chain_info().unwrap();
+        assert_eq!(chain_info.head_block, "100");
+        assert_eq!(chain_info.lib_block, "90");
+        client.get_node_info().unwrap();
+        client.get_gas_ratio().unwrap();
+        client.get_ram_info().unwrap();
+        assert_eq!(
+            client.get_tx_by_hash("abc").unwrap().transaction.hash,
+            "abc"
+        );
+        client.get_tx_receipt_by_tx_hash("abc").unwrap();
+        client.get_account("admin", true).unwrap();
+        client.get_token_balance("admin", "iost", true).unwrap();
+        client.get_token_info("iost", true).unwrap();
+        client.get_contract("token.iost", true).unwrap();
+        client.get_producer_vote_info("producer00", true).unwrap();
+        client.get_candidate_bonus("producer00", true).unwrap();
+        client.get_voter_bonus("admin", true).unwrap();
+        client
+            .get_contract_storage(&ContractStoragePost {
+                id: "token.iost".to_string(),
+                key: "TIiost".to_string(),
+                field: "decimal".to_string(),
+                by_longest_chain: true,
+            })
+            .unwrap();
+        client
+            .get_contract_storage_fields(&ContractStorageFieldsPost {
+                id: "token.iost".to_string(),
+                key: "TIiost".to_string(),
+                by_longest_chain: true,
+            })
+            .unwrap();
+        client
+            .get_batch_contract_storage(&BatchContractStoragePost {
+                id: "token.iost".to_string(),
+                key_fields: vec![],
+                by_longest_chain: true,
+            })
+            .unwrap();
+
+        let block = client.get_block_by_number(95, true).unwrap();
+        assert_eq!(block.status, Status::PENDING);
+        assert_eq!(block.block.transactions.len(), 1);
+        let parent = client
+            .get_block_by_hash(&block.block.parent_hash, false)
+            .unwrap();
+        assert_eq!(parent.block.number, "94");
+
+        node.set_head_block(200);
+        node.set_lib_block(195);
+        assert_eq!(client.get_chain_info().unwrap().head_block, "200");
+        assert_eq!(
+            client.get_block_by_number(95, false).unwrap().status,
+            Status::IRREVERSIBLE
+        );
+        let estimate = client.estimate_tx(&signed_tx(), 1.2).unwrap();
+        assert_eq!(estimate.gas_usage, 2577.0);
+
+        let requests = node.requests();
+        assert_eq!(requests.len(), 21);
+        assert!(requests
+            .iter()
+            .any(|request| request.method == "POST" && request.path == "getContractStorage"));
+    }
+
+    #[test]
+    fn should_report_missing_blocks() {
+        let node = MockNode::start().unwrap();
+        match client(&node).get_block_by_number(101, false) {
+            Err(iost_chain::Error::NodeError(kind, _)) => assert_eq!(kind, NodeErrorKind::NotFound),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_accept_signed_tx() {
+        let node = MockNode::start().unwrap();
+        let response = client(&node).send_tx(&signed_tx()).unwrap();
+
+        let sent = node.sent_txs();
+        assert_eq!(sent.len(), 1);
+        let tx = sent[0].tx.as_ref().unwrap();
+        assert_eq!(tx.hash().unwrap().to_string(), response.hash);
+        assert_eq!(sent[0].result, Ok(response.hash));
+        assert_eq!(tx.publisher, "admin");
+    }
+
+    #[test]
+    fn should_confirm_sent_tx() {
+        let node = MockNode::start().unwrap();
+        let confirmation = client(&node)
+            .send_and_confirm(&signed_tx(), TrackerConfig::default())
+            .unwrap();
+        assert_eq!(confirmation.status, Status::IRREVERSIBLE);
+        assert_eq!(confirmation.block_number, 90);
+        assert_eq!(
+            confirmation.hash,
+            node.sent_txs()[0].result.clone().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_reject_tampered_tx() {
+        let node = MockNode::start().unwrap();
+        let mut tx = signed_tx();
+        tx.gas_limit += 1.0;
+
+        match client(&node).send_tx(&tx) {
+            Err(iost_chain::Error::NodeError(kind, _)) => {
+                assert_eq!(kind, NodeErrorKind::InvalidRequest)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(node.sent_txs()[0].result.is_err());
+    }
+
+    #[test]
+    fn should_use_programmed_responses() {
+        let node = MockNode::start().unwrap();
+        node.set_response("getGasRatio", MockResponse::error(503, 14, "node is busy"));
+        node.set_handler("getTxReceiptByTxHash", |request| {
+            let mut receipt = fixtures::tx_receipt(request.segments()[1]);
+            receipt["status_code"] = "BALANCE_NOT_ENOUGH".into();
+            MockResponse::ok(receipt)
+        });
+        let client = client(&node);
+
+        assert!(client.get_gas_ratio().is_err());
+        let receipt = client.get_tx_receipt_by_tx_hash("abc").unwrap();
+        assert_eq!(receipt.tx_hash, "abc");
+        assert_eq!(receipt.status.code, 2);
+
+        node.clear_handlers();
+        client.get_gas_ratio().unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_serve_async_client() {
+        let node = MockNode::start().unwrap();
+        let client = IostClient::new(&node.url());
+        let info = client.get_chain_info().await.unwrap();
+        assert_eq!(info.chain_id, fixtures::CHAIN_ID);
+        assert_eq!(node.requests()[0].path, "getChainInfo");
+    }
+}
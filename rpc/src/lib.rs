@@ -105,7 +105,7 @@ impl Client for IOST {
               R: Serialize + Send + Sync
     {
         let url = format!("{}/{}", self.host, path);
-        let req = reqwest::Client::new()
+        let req = self.client
             .post(&url)
             .json(&param)
             .send()
@@ -122,6 +122,14 @@ impl Client for IOST {
 }
 
 impl IOST {
+    /// Use a configured client, e.g. with timeouts or default headers, for every request.
+    pub fn with_client(host: &str, client: reqwest::Client) -> Self {
+        Self {
+            host: host.to_owned(),
+            client,
+        }
+    }
+
     pub async fn get_node_info(&self) -> Result<NodeInfo, Error> {
         self.get("getNodeInfo").await
     }